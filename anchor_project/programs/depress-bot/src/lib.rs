@@ -0,0 +1,233 @@
+#![allow(deprecated)]
+#![allow(unexpected_cfgs)]
+//-------------------------------------------------------------------------------
+///
+/// Example consumer of the DePress CPI interface.
+///
+/// The bot posts, comments and reacts as a PDA author derived from its admin
+/// wallet with seeds: [BOT_AUTHOR_SEED, admin pubkey]. The PDA signs the DePress
+/// instructions through `invoke_signed`, while the admin wallet pays the rent of
+/// the created accounts as the DePress `payer`.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use depress::cpi::accounts::{AddCommentContext, AddPostContext, AddReactionPostContext};
+use depress::program::Depress;
+use depress::states::ContentLabels;
+
+declare_id!("EqrDt79iqFaiiH9XtmVP2HVkM3e8pv6PCQvc3ZGpDy5j");
+
+pub const BOT_AUTHOR_SEED: &str = "BOT_AUTHOR_SEED";
+
+#[program]
+pub mod depress_bot {
+    use super::*;
+
+    pub fn bot_post<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BotPostContext<'info>>,
+        post_topic: String,
+        post_content: String,
+    ) -> Result<()> {
+        let admin = ctx.accounts.admin.key();
+        let signer_seeds: &[&[u8]] = &[
+            BOT_AUTHOR_SEED.as_bytes(),
+            admin.as_ref(),
+            &[ctx.bumps.bot_author],
+        ];
+
+        let signer = &[signer_seeds];
+
+        // Hashtag index accounts are forwarded untouched
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.depress_program.to_account_info(),
+            AddPostContext {
+                post_author: ctx.accounts.bot_author.to_account_info(),
+                payer: ctx.accounts.admin.to_account_info(),
+                post: ctx.accounts.post.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                quoted_post: None,
+                session: None,
+                config: ctx.accounts.config.to_account_info(),
+                event_authority: ctx.accounts.event_authority.to_account_info(),
+                program: ctx.accounts.depress_program.to_account_info(),
+                post_index: ctx.accounts.post_index.to_account_info(),
+            },
+            signer,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+        depress::cpi::post_add(
+            cpi_ctx,
+            post_topic,
+            post_content,
+            Vec::new(),
+            None,
+            Vec::new(),
+            ContentLabels::default(),
+            None,
+            None,
+            false,
+            Vec::new(),
+        )
+    }
+
+    pub fn bot_comment(ctx: Context<BotCommentContext>, comment_content: String) -> Result<()> {
+        let admin = ctx.accounts.admin.key();
+        let signer_seeds: &[&[u8]] = &[
+            BOT_AUTHOR_SEED.as_bytes(),
+            admin.as_ref(),
+            &[ctx.bumps.bot_author],
+        ];
+        let signer = &[signer_seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.depress_program.to_account_info(),
+            AddCommentContext {
+                comment_author: ctx.accounts.bot_author.to_account_info(),
+                payer: ctx.accounts.admin.to_account_info(),
+                post: ctx.accounts.post.to_account_info(),
+                comment: ctx.accounts.comment.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                session: None,
+                config: Some(ctx.accounts.config.to_account_info()),
+                event_authority: ctx.accounts.event_authority.to_account_info(),
+                program: ctx.accounts.depress_program.to_account_info(),
+                hook_program: None,
+            },
+            signer,
+        );
+
+        depress::cpi::comment_add(
+            cpi_ctx,
+            comment_content,
+            Vec::new(),
+            None,
+            ContentLabels::default(),
+        )
+    }
+
+    pub fn bot_like(ctx: Context<BotLikeContext>) -> Result<()> {
+        let admin = ctx.accounts.admin.key();
+        let signer_seeds: &[&[u8]] = &[
+            BOT_AUTHOR_SEED.as_bytes(),
+            admin.as_ref(),
+            &[ctx.bumps.bot_author],
+        ];
+        let signer = &[signer_seeds];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.depress_program.to_account_info(),
+            AddReactionPostContext {
+                post_reaction: ctx.accounts.post_reaction.to_account_info(),
+                post: ctx.accounts.post.to_account_info(),
+                reaction_author: ctx.accounts.bot_author.to_account_info(),
+                payer: ctx.accounts.admin.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                session: None,
+                config: Some(ctx.accounts.config.to_account_info()),
+                event_authority: ctx.accounts.event_authority.to_account_info(),
+                program: ctx.accounts.depress_program.to_account_info(),
+                hook_program: None,
+            },
+            signer,
+        );
+
+        depress::cpi::like_post(cpi_ctx)
+    }
+}
+
+#[derive(Accounts)]
+pub struct BotPostContext<'info> {
+    // Controls the bot and pays the rent
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: PDA author signing the DePress instruction, holds no data.
+    #[account(
+        seeds = [BOT_AUTHOR_SEED.as_bytes(), admin.key().as_ref()],
+        bump
+    )]
+    pub bot_author: UncheckedAccount<'info>,
+
+    /// CHECK: Created and validated by DePress.
+    #[account(mut)]
+    pub post: UncheckedAccount<'info>,
+
+    /// CHECK: Created and validated by DePress.
+    #[account(mut)]
+    pub post_index: UncheckedAccount<'info>,
+
+    /// CHECK: DePress config numbering the events, validated by DePress.
+    #[account(mut)]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: DePress event authority signing the event self-CPI, validated by DePress.
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub depress_program: Program<'info, Depress>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BotCommentContext<'info> {
+    // Controls the bot and pays the rent
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: PDA author signing the DePress instruction, holds no data.
+    #[account(
+        seeds = [BOT_AUTHOR_SEED.as_bytes(), admin.key().as_ref()],
+        bump
+    )]
+    pub bot_author: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by DePress.
+    #[account(mut)]
+    pub post: UncheckedAccount<'info>,
+
+    /// CHECK: Created and validated by DePress.
+    #[account(mut)]
+    pub comment: UncheckedAccount<'info>,
+
+    /// CHECK: DePress config numbering the events, validated by DePress.
+    #[account(mut)]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: DePress event authority signing the event self-CPI, validated by DePress.
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub depress_program: Program<'info, Depress>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BotLikeContext<'info> {
+    // Controls the bot and pays the rent
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: PDA author signing the DePress instruction, holds no data.
+    #[account(
+        seeds = [BOT_AUTHOR_SEED.as_bytes(), admin.key().as_ref()],
+        bump
+    )]
+    pub bot_author: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by DePress.
+    #[account(mut)]
+    pub post: UncheckedAccount<'info>,
+
+    /// CHECK: Created and validated by DePress.
+    #[account(mut)]
+    pub post_reaction: UncheckedAccount<'info>,
+
+    /// CHECK: DePress config numbering the events, validated by DePress.
+    #[account(mut)]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: DePress event authority signing the event self-CPI, validated by DePress.
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub depress_program: Program<'info, Depress>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,46 @@
+#![allow(deprecated)]
+#![allow(unexpected_cfgs)]
+//-------------------------------------------------------------------------------
+///
+/// Example DePress engagement hook.
+///
+/// A post author registers this program with `post_set_hook`; DePress then
+/// invokes `on_engagement` on every comment and reaction on the post, with the
+/// post as only account. The hook accepts comments and likes and rejects
+/// dislikes, which makes DePress fail the whole instruction.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use depress::states::{Engagement, EngagementKind, ReactionType};
+
+declare_id!("J3Kv4dLnLgdwLNefyaquWnMYjW6xLeo4z6Lx21b82D2n");
+
+#[program]
+pub mod depress_hook {
+    use super::*;
+
+    pub fn on_engagement(_ctx: Context<OnEngagementContext>, engagement: Engagement) -> Result<()> {
+        match engagement.kind {
+            EngagementKind::PostReaction(ReactionType::Dislike)
+            | EngagementKind::CommentReaction(ReactionType::Dislike) => {
+                err!(HookError::DislikesDisabled)
+            }
+            _ => {
+                msg!("Engagement by {} on {}", engagement.actor, engagement.post);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct OnEngagementContext<'info> {
+    /// CHECK: The engaged post, passed read-only by DePress.
+    pub post: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum HookError {
+    #[msg("Dislikes are disabled on this post")]
+    DislikesDisabled,
+}
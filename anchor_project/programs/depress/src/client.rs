@@ -0,0 +1,367 @@
+//! Off-chain helpers for programs and services reading DePress accounts and events.
+//!
+//! Only compiled for host targets, never into the on-chain program.
+
+use std::fmt;
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+
+use crate::instructions::*;
+use crate::states::{ContentRef, EventHeader, Post};
+
+/// Retrieves raw bytes for a content URI (`ar://`, `ipfs://`, `https://`, `http://`).
+///
+/// Implement this on top of the HTTP client and gateways of your choice.
+pub trait ContentFetcher {
+    type Error;
+
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, Self::Error>;
+}
+
+impl<E, F> ContentFetcher for F
+where
+    F: Fn(&str) -> Result<Vec<u8>, E>,
+{
+    type Error = E;
+
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, E> {
+        self(uri)
+    }
+}
+
+#[derive(Debug)]
+pub enum ContentError<E> {
+    /// The post stores its content inline.
+    NotOffChain,
+    /// The fetcher failed to retrieve the body.
+    Fetch(E),
+    /// The body does not match the committed `byte_length` and `content_hash`.
+    Mismatch,
+}
+
+impl<E: fmt::Display> fmt::Display for ContentError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentError::NotOffChain => write!(f, "post content is stored inline"),
+            ContentError::Fetch(err) => write!(f, "failed to fetch content: {err}"),
+            ContentError::Mismatch => {
+                write!(f, "content does not match its committed length and hash")
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ContentError<E> {}
+
+/// Fetches an off-chain body and verifies it against its on-chain commitment.
+pub fn fetch_content<F: ContentFetcher>(
+    fetcher: &F,
+    content_ref: &ContentRef,
+) -> Result<Vec<u8>, ContentError<F::Error>> {
+    let body = fetcher
+        .fetch(&content_ref.uri)
+        .map_err(ContentError::Fetch)?;
+    if !content_ref.verify(&body) {
+        return Err(ContentError::Mismatch);
+    }
+
+    Ok(body)
+}
+
+/// Fetches and verifies the off-chain body of `post`.
+pub fn fetch_post_content<F: ContentFetcher>(
+    fetcher: &F,
+    post: &Post,
+) -> Result<Vec<u8>, ContentError<F::Error>> {
+    let content_ref = post.content_ref.as_ref().ok_or(ContentError::NotOffChain)?;
+    fetch_content(fetcher, content_ref)
+}
+
+macro_rules! depress_events {
+    ($($event:ident),* $(,)?) => {
+        /// An event emitted by DePress, decoded from the data of its self-CPI.
+        pub enum DepressEvent {
+            $($event($event),)*
+        }
+
+        impl DepressEvent {
+            /// Header shared by every event, ordering them through `sequence`.
+            pub fn header(&self) -> &EventHeader {
+                match self {
+                    $(DepressEvent::$event(event) => &event.header,)*
+                }
+            }
+
+            fn decode(data: &[u8]) -> Result<Self, EventDecodeError> {
+                $(
+                    if let Some(body) = data.strip_prefix($event::DISCRIMINATOR) {
+                        return $event::try_from_slice(body)
+                            .map(DepressEvent::$event)
+                            .map_err(EventDecodeError::InvalidData);
+                    }
+                )*
+                Err(EventDecodeError::UnknownEvent(data.iter().take(8).copied().collect()))
+            }
+        }
+    };
+}
+
+depress_events!(
+    AccountMigrated,
+    BatchReactionSkipped,
+    ChunkAppended,
+    CoAuthorApproved,
+    CommentCreated,
+    CommentLabelsEdited,
+    CommentRemoved,
+    DraftSaved,
+    Mentioned,
+    PollClosed,
+    PollCreated,
+    PollVoted,
+    PollVoteRemoved,
+    PostCreated,
+    PostEdited,
+    PostExpired,
+    PostFinalized,
+    PostHookSet,
+    PostPinned,
+    PostPublished,
+    PostRemoved,
+    PostTipped,
+    PostUnpinned,
+    QuotePostCreated,
+    ReactionCommentAdded,
+    ReactionCommentRemoved,
+    ReactionPostAdded,
+    ReactionPostRemoved,
+    RemovalVoted,
+    RepostCreated,
+    RepostRemoved,
+    RevisionPruned,
+    SessionCreated,
+    SessionRevoked,
+);
+
+#[derive(Debug)]
+pub enum EventDecodeError {
+    /// The discriminator matches no event of this schema version.
+    UnknownEvent(Vec<u8>),
+    /// The event body does not deserialize.
+    InvalidData(std::io::Error),
+}
+
+impl fmt::Display for EventDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventDecodeError::UnknownEvent(discriminator) => {
+                write!(f, "unknown event discriminator {discriminator:?}")
+            }
+            EventDecodeError::InvalidData(err) => write!(f, "invalid event data: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for EventDecodeError {}
+
+/// Decodes the data of an inner instruction to DePress.
+///
+/// Returns `None` when the instruction is not an event self-CPI.
+pub fn decode_event(data: &[u8]) -> Option<Result<DepressEvent, EventDecodeError>> {
+    data.strip_prefix(EVENT_IX_TAG_LE).map(DepressEvent::decode)
+}
+
+/// Extracts the DePress events, in emission order, from the inner instructions
+/// of a successful transaction given as `(program id, raw data)` pairs.
+///
+/// RPC nodes return inner instruction data base58 encoded and the program as an
+/// index into the transaction account keys; resolve both before calling this.
+/// Events of failed transactions were rolled back and must not be decoded.
+pub fn decode_events<'a, I>(inner_instructions: I) -> Result<Vec<DepressEvent>, EventDecodeError>
+where
+    I: IntoIterator<Item = (&'a Pubkey, &'a [u8])>,
+{
+    inner_instructions
+        .into_iter()
+        .filter(|(program_id, _)| **program_id == crate::ID)
+        .filter_map(|(_, data)| decode_event(data))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::Event;
+
+    use super::*;
+    use crate::constants::EVENT_SCHEMA_VERSION;
+    use crate::states::payerless;
+
+    const BODY: &[u8] = b"A long-form body stored on Arweave";
+
+    fn content_ref(body: &[u8]) -> ContentRef {
+        ContentRef {
+            uri: "ar://body".to_string(),
+            content_hash: hash(body).to_bytes(),
+            mime_type: "text/plain".to_string(),
+            byte_length: body.len() as u64,
+        }
+    }
+
+    fn serve(body: &[u8]) -> impl Fn(&str) -> Result<Vec<u8>, String> {
+        let body = body.to_vec();
+        move |uri: &str| {
+            assert_eq!(uri, "ar://body");
+            Ok(body.clone())
+        }
+    }
+
+    #[test]
+    fn fetches_matching_content() {
+        let body = fetch_content(&serve(BODY), &content_ref(BODY)).unwrap();
+        assert_eq!(body, BODY);
+    }
+
+    #[test]
+    fn rejects_content_with_another_hash() {
+        let mut swapped = BODY.to_vec();
+        swapped[0] ^= 1;
+
+        assert!(matches!(
+            fetch_content(&serve(&swapped), &content_ref(BODY)),
+            Err(ContentError::Mismatch)
+        ));
+    }
+
+    #[test]
+    fn rejects_content_with_another_length() {
+        let mut committed = content_ref(BODY);
+        committed.byte_length += 1;
+
+        assert!(matches!(
+            fetch_content(&serve(BODY), &committed),
+            Err(ContentError::Mismatch)
+        ));
+    }
+
+    #[test]
+    fn forwards_fetch_errors() {
+        let unreachable = |_: &str| -> Result<Vec<u8>, String> { Err("timeout".to_string()) };
+
+        match fetch_content(&unreachable, &content_ref(BODY)) {
+            Err(ContentError::Fetch(err)) => assert_eq!(err, "timeout"),
+            _ => panic!("expected a fetch error"),
+        }
+    }
+
+    #[test]
+    fn fetches_post_content_only_off_chain() {
+        let mut post: Post = payerless::Post {
+            post_author: Pubkey::new_unique(),
+            topic: "Off-chain".to_string(),
+            content: String::new(),
+            likes: 0,
+            dislikes: 0,
+            comment_count: 0,
+            bump: 255,
+        }
+        .into();
+        assert!(matches!(
+            fetch_post_content(&serve(BODY), &post),
+            Err(ContentError::NotOffChain)
+        ));
+
+        post.content_ref = Some(content_ref(BODY));
+        assert_eq!(fetch_post_content(&serve(BODY), &post).unwrap(), BODY);
+    }
+
+    fn finalized() -> PostFinalized {
+        PostFinalized {
+            header: EventHeader {
+                version: EVENT_SCHEMA_VERSION,
+                sequence: 7,
+                slot: 42,
+                timestamp: 1_700_000_000,
+            },
+            post: Pubkey::new_unique(),
+            chunk_count: 2,
+            total_length: 1_300,
+        }
+    }
+
+    fn self_cpi_data(event: &impl Event) -> Vec<u8> {
+        [EVENT_IX_TAG_LE, &event.data()].concat()
+    }
+
+    #[test]
+    fn decodes_self_cpi_event() {
+        let event = finalized();
+
+        match decode_event(&self_cpi_data(&event)) {
+            Some(Ok(DepressEvent::PostFinalized(decoded))) => {
+                assert_eq!(decoded.header.sequence, 7);
+                assert_eq!(decoded.header.timestamp, 1_700_000_000);
+                assert_eq!(decoded.post, event.post);
+                assert_eq!(decoded.chunk_count, 2);
+                assert_eq!(decoded.total_length, 1_300);
+            }
+            _ => panic!("expected a decoded PostFinalized"),
+        }
+    }
+
+    #[test]
+    fn skips_data_without_event_tag() {
+        // An instruction to DePress itself, e.g. a CPI from another program
+        let mut data = self_cpi_data(&finalized());
+        data[0] ^= 0xff;
+
+        assert!(decode_event(&data).is_none());
+        assert!(decode_event(&[]).is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_discriminator() {
+        let data = [EVENT_IX_TAG_LE, &[0xff; 8], &[1, 2, 3]].concat();
+
+        match decode_event(&data) {
+            Some(Err(EventDecodeError::UnknownEvent(discriminator))) => {
+                assert_eq!(discriminator, vec![0xff; 8]);
+            }
+            _ => panic!("expected an unknown event"),
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_event() {
+        let data = self_cpi_data(&finalized());
+
+        assert!(matches!(
+            decode_event(&data[..data.len() - 1]),
+            Some(Err(EventDecodeError::InvalidData(_)))
+        ));
+    }
+
+    #[test]
+    fn decodes_events_of_depress_only_in_order() {
+        let first = self_cpi_data(&finalized());
+        let other_program = Pubkey::new_unique();
+        let second = self_cpi_data(&SessionRevoked {
+            header: finalized().header,
+            authority: Pubkey::new_unique(),
+            session_key: Pubkey::new_unique(),
+        });
+        let inner = [
+            (crate::ID, &first[..]),
+            (other_program, &first[..]),
+            (crate::ID, &[1, 2, 3][..]),
+            (crate::ID, &second[..]),
+        ];
+
+        let events = decode_events(inner.iter().map(|(id, data)| (id, *data))).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], DepressEvent::PostFinalized(_)));
+        assert!(matches!(events[1], DepressEvent::SessionRevoked(_)));
+    }
+}
@@ -6,8 +6,142 @@ pub const TOPIC_LENGTH: usize = 32;
 pub const CONTENT_LENGTH: usize = 500;
 pub const COMMENT_LENGTH: usize = 100;
 
+// Long-form posts
+pub const CHUNK_LENGTH: usize = 900;
+pub const MAX_POST_CHUNKS: u32 = 16;
+
+// Off-chain content
+pub const URI_LENGTH: usize = 200;
+pub const MIME_TYPE_LENGTH: usize = 64;
+pub const URI_SCHEMES: [&str; 4] = ["ar://", "ipfs://", "https://", "http://"];
+
+// Attachments
+pub const MAX_POST_ATTACHMENTS: usize = 4;
+pub const ALT_TEXT_LENGTH: usize = 100;
+
+// Content labels
+pub const LANGUAGE_LENGTH: usize = 16;
+pub const CONTENT_WARNING_LENGTH: usize = 64;
+pub const FLAG_NSFW: u8 = 1 << 0;
+pub const FLAG_SPOILER: u8 = 1 << 1;
+pub const FLAG_SPONSORED: u8 = 1 << 2;
+pub const FLAG_AI_GENERATED: u8 = 1 << 3;
+pub const CONTENT_FLAGS: u8 = FLAG_NSFW | FLAG_SPOILER | FLAG_SPONSORED | FLAG_AI_GENERATED;
+
+// Hashtags
+pub const MAX_HASHTAGS: usize = 5;
+pub const HASHTAG_LENGTH: usize = 32;
+
+// Mentions
+pub const MAX_MENTIONS: usize = 5;
+
+// Polls
+pub const MIN_POLL_OPTIONS: usize = 2;
+pub const MAX_POLL_OPTIONS: usize = 4;
+pub const POLL_OPTION_LENGTH: usize = 32;
+
+// Profiles
+pub const MAX_PINNED_POSTS: usize = 3;
+
+// Co-authors
+pub const MAX_CO_AUTHORS: usize = 4;
+pub const SHARE_BASIS_POINTS: u16 = 10_000;
+
+// Sessions
+pub const SESSION_REACT: u8 = 1;
+pub const SESSION_COMMENT: u8 = 2;
+pub const SESSION_POST: u8 = 4;
+pub const SESSION_ACTIONS: u8 = SESSION_REACT | SESSION_COMMENT | SESSION_POST;
+pub const MAX_SESSION_DURATION: i64 = 7 * 24 * 60 * 60;
+
+// Events, bumped on every breaking change to an event's fields
+pub const EVENT_SCHEMA_VERSION: u8 = 2;
+
+// Engagement hooks, the Anchor discriminator of `on_engagement`
+pub const HOOK_DISCRIMINATOR: [u8; 8] = [41, 173, 9, 133, 38, 19, 35, 77];
+
+// Batch reactions
+pub const MAX_BATCH_REACTIONS: usize = 16;
+
+// Account versions, the leading `version` byte of each account. Bump one on every
+// change to its layout and teach `migrate_account` the previous layout
+pub const POST_VERSION: u8 = 1;
+pub const COMMENT_VERSION: u8 = 1;
+pub const REACTION_VERSION: u8 = 1;
+pub const TAG_VERSION: u8 = 1;
+pub const REPOST_VERSION: u8 = 1;
+pub const POLL_VERSION: u8 = 1;
+pub const POLL_VOTE_VERSION: u8 = 1;
+pub const PROFILE_VERSION: u8 = 1;
+pub const REVISION_VERSION: u8 = 1;
+pub const CHUNK_VERSION: u8 = 1;
+pub const SESSION_VERSION: u8 = 1;
+pub const NONCE_VERSION: u8 = 1;
+pub const CONFIG_VERSION: u8 = 1;
+pub const INDEX_VERSION: u8 = 1;
+
+// Account discriminators. `Post` and `Comment` moved to fixed-offset layouts under
+// the Anchor discriminators of `PostV2` and `CommentV2`; accounts still carrying those
+// of `Post` and `Comment` are in the 0.1 layouts, rewritten by `migrate_account`
+pub const POST_DISCRIMINATOR: &[u8] = &[157, 93, 162, 77, 117, 204, 25, 194];
+pub const COMMENT_DISCRIMINATOR: &[u8] = &[10, 253, 101, 165, 235, 245, 153, 135];
+pub const LEGACY_POST_DISCRIMINATOR: &[u8] = &[8, 147, 90, 186, 185, 56, 192, 150];
+pub const LEGACY_COMMENT_DISCRIMINATOR: &[u8] = &[150, 135, 96, 244, 55, 199, 50, 65];
+
+// Account layouts, byte offsets (discriminator included) of the fixed-size fields
+// for `memcmp` filters
+pub const ACCOUNT_VERSION_OFFSET: usize = DISCRIMINATOR;
+
+pub const POST_AUTHOR_OFFSET: usize = ACCOUNT_VERSION_OFFSET + 1;
+pub const POST_PAYER_OFFSET: usize = POST_AUTHOR_OFFSET + 32;
+pub const POST_LIKES_OFFSET: usize = POST_PAYER_OFFSET + 32;
+pub const POST_DISLIKES_OFFSET: usize = POST_LIKES_OFFSET + 8;
+pub const POST_COMMENT_COUNT_OFFSET: usize = POST_DISLIKES_OFFSET + 8;
+pub const POST_REPOST_COUNT_OFFSET: usize = POST_COMMENT_COUNT_OFFSET + 4;
+pub const POST_REVISION_COUNT_OFFSET: usize = POST_REPOST_COUNT_OFFSET + 4;
+pub const POST_CHUNK_COUNT_OFFSET: usize = POST_REVISION_COUNT_OFFSET + 4;
+pub const POST_TOTAL_LENGTH_OFFSET: usize = POST_CHUNK_COUNT_OFFSET + 4;
+pub const POST_GLOBAL_ID_OFFSET: usize = POST_TOTAL_LENGTH_OFFSET + 4;
+pub const POST_HAS_POLL_OFFSET: usize = POST_GLOBAL_ID_OFFSET + 8;
+pub const POST_IS_PINNED_OFFSET: usize = POST_HAS_POLL_OFFSET + 1;
+pub const POST_FINALIZED_OFFSET: usize = POST_IS_PINNED_OFFSET + 1;
+pub const POST_PUBLISHED_OFFSET: usize = POST_FINALIZED_OFFSET + 1;
+pub const POST_IS_DRAFT_OFFSET: usize = POST_PUBLISHED_OFFSET + 1;
+pub const POST_REMOVAL_VOTES_OFFSET: usize = POST_IS_DRAFT_OFFSET + 1;
+pub const POST_BUMP_OFFSET: usize = POST_REMOVAL_VOTES_OFFSET + 1;
+// First variable-size field, `topic`
+pub const POST_TOPIC_OFFSET: usize = POST_BUMP_OFFSET + 1;
+
+pub const COMMENT_AUTHOR_OFFSET: usize = ACCOUNT_VERSION_OFFSET + 1;
+pub const COMMENT_PAYER_OFFSET: usize = COMMENT_AUTHOR_OFFSET + 32;
+pub const COMMENT_PARENT_POST_OFFSET: usize = COMMENT_PAYER_OFFSET + 32;
+pub const COMMENT_LIKES_OFFSET: usize = COMMENT_PARENT_POST_OFFSET + 32;
+pub const COMMENT_DISLIKES_OFFSET: usize = COMMENT_LIKES_OFFSET + 8;
+pub const COMMENT_BUMP_OFFSET: usize = COMMENT_DISLIKES_OFFSET + 8;
+// First variable-size field, `content`
+pub const COMMENT_CONTENT_OFFSET: usize = COMMENT_BUMP_OFFSET + 1;
+
+// `ReactionPost` and `ReactionComment` share one layout, fixed-size throughout
+pub const REACTION_AUTHOR_OFFSET: usize = ACCOUNT_VERSION_OFFSET + 1;
+pub const REACTION_PAYER_OFFSET: usize = REACTION_AUTHOR_OFFSET + 32;
+// `parent_post` or `parent_comment`
+pub const REACTION_PARENT_OFFSET: usize = REACTION_PAYER_OFFSET + 32;
+pub const REACTION_TYPE_OFFSET: usize = REACTION_PARENT_OFFSET + 32;
+pub const REACTION_BUMP_OFFSET: usize = REACTION_TYPE_OFFSET + 1;
+
 // PDA seeds
 pub const POST_SEED: &str = "POST_SEED";
 pub const POST_REACTION_SEED: &str = "POST_REACTION_SEED";
 pub const COMMENT_SEED: &str = "COMMENT_SEED";
 pub const COMMENT_REACTION_SEED: &str = "COMMENT_REACTION_SEED";
+pub const TAG_SEED: &str = "TAG_SEED";
+pub const REPOST_SEED: &str = "REPOST_SEED";
+pub const POLL_SEED: &str = "POLL_SEED";
+pub const POLL_VOTE_SEED: &str = "POLL_VOTE_SEED";
+pub const PROFILE_SEED: &str = "PROFILE_SEED";
+pub const REVISION_SEED: &str = "REVISION_SEED";
+pub const CHUNK_SEED: &str = "CHUNK_SEED";
+pub const SESSION_SEED: &str = "SESSION_SEED";
+pub const NONCE_SEED: &str = "NONCE_SEED";
+pub const CONFIG_SEED: &str = "CONFIG_SEED";
+pub const INDEX_SEED: &str = "INDEX_SEED";
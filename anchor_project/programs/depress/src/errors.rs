@@ -22,4 +22,158 @@ pub enum DepressError {
     CommentTooLong,
     #[msg("Maximum number of comments reached")]
     TooManyComments,
+    #[msg("Too many hashtags in content")]
+    TooManyHashtags,
+    #[msg("Hashtag too long")]
+    HashtagTooLong,
+    #[msg("Missing tag account for hashtag")]
+    MissingTagAccount,
+    #[msg("Invalid tag account")]
+    InvalidTagAccount,
+    #[msg("Too many mentions")]
+    TooManyMentions,
+    #[msg("Duplicate mention")]
+    DuplicateMention,
+    #[msg("Cannot mention yourself")]
+    SelfMention,
+    #[msg("Original post still exists")]
+    OriginalPostExists,
+    #[msg("Invalid number of poll options")]
+    InvalidPollOptions,
+    #[msg("Poll option too long")]
+    PollOptionTooLong,
+    #[msg("Poll closing time must be in the future")]
+    InvalidPollClosingTime,
+    #[msg("Poll is closed")]
+    PollClosed,
+    #[msg("Invalid poll option")]
+    InvalidPollOption,
+    #[msg("Post has a poll that must be closed with it")]
+    PollAccountRequired,
+    #[msg("Maximum number of pinned posts reached")]
+    TooManyPinnedPosts,
+    #[msg("Post is already pinned")]
+    PostAlreadyPinned,
+    #[msg("Post is not pinned")]
+    PostNotPinned,
+    #[msg("Post is pinned, the author's profile must be passed")]
+    ProfileAccountRequired,
+    #[msg("Chunk too long")]
+    ChunkTooLong,
+    #[msg("Maximum number of chunks reached")]
+    TooManyChunks,
+    #[msg("Post is finalized")]
+    PostFinalized,
+    #[msg("Missing chunk account")]
+    MissingChunkAccount,
+    #[msg("Invalid chunk account")]
+    InvalidChunkAccount,
+    #[msg("Content URI too long")]
+    UriTooLong,
+    #[msg("Unsupported content URI scheme")]
+    InvalidUriScheme,
+    #[msg("Invalid MIME type")]
+    InvalidMimeType,
+    #[msg("Invalid content length")]
+    InvalidContentLength,
+    #[msg("Off-chain posts cannot have inline content")]
+    InlineContentNotAllowed,
+    #[msg("Too many attachments")]
+    TooManyAttachments,
+    #[msg("Alt text too long")]
+    AltTextTooLong,
+    #[msg("Invalid language tag")]
+    InvalidLanguageTag,
+    #[msg("Content warning too long")]
+    ContentWarningTooLong,
+    #[msg("Unknown content flags")]
+    InvalidContentFlags,
+    #[msg("Publish time must be in the future")]
+    InvalidPublishTime,
+    #[msg("Post is not published yet")]
+    PostNotPublished,
+    #[msg("Post is already published")]
+    PostAlreadyPublished,
+    #[msg("Expiry time must be in the future and after the publishing time")]
+    InvalidExpiryTime,
+    #[msg("Post has expired")]
+    PostExpired,
+    #[msg("Post has not expired yet")]
+    PostNotExpired,
+    #[msg("Comment does not belong to the given post")]
+    InvalidParentPost,
+    #[msg("Post is a draft")]
+    PostIsDraft,
+    #[msg("Post is not a draft")]
+    PostNotDraft,
+    #[msg("Too many co-authors")]
+    TooManyCoAuthors,
+    #[msg("Duplicate co-author")]
+    DuplicateCoAuthor,
+    #[msg("Co-author shares exceed 100%")]
+    InvalidCoAuthorShares,
+    #[msg("Signer is not a co-author of the post")]
+    NotCoAuthor,
+    #[msg("Co-author has already approved the post")]
+    CoAuthorAlreadyApproved,
+    #[msg("Signer is not an author of the post")]
+    NotPostAuthor,
+    #[msg("Removal already voted")]
+    RemovalAlreadyVoted,
+    #[msg("Removal has not been approved by a majority of the authors")]
+    RemovalNotApproved,
+    #[msg("Missing co-author account")]
+    MissingCoAuthorAccount,
+    #[msg("Invalid co-author account")]
+    InvalidCoAuthorAccount,
+    #[msg("Tip amount must be positive")]
+    InvalidTipAmount,
+    #[msg("Invalid session permissions")]
+    InvalidSessionPermissions,
+    #[msg("Session expiry must be in the future and within the maximum duration")]
+    InvalidSessionExpiry,
+    #[msg("Signer is not the session key")]
+    InvalidSessionKey,
+    #[msg("Session has expired")]
+    SessionExpired,
+    #[msg("Session does not permit this action")]
+    SessionNotPermitted,
+    #[msg("Accounts of a post must be paid for by the post's payer")]
+    InvalidPayer,
+    #[msg("Missing Ed25519 signature verification instruction")]
+    MissingSignatureInstruction,
+    #[msg("Signature verification does not match the author or message")]
+    InvalidSignatureInstruction,
+    #[msg("Invalid relay nonce")]
+    InvalidRelayNonce,
+    #[msg("Batch is empty or exceeds the maximum number of reactions")]
+    InvalidBatchSize,
+    #[msg("Missing account for a batch reaction")]
+    MissingBatchAccount,
+    #[msg("Invalid reaction account")]
+    InvalidReactionAccount,
+    #[msg("Invalid hook program")]
+    InvalidHookProgram,
+    #[msg("Missing the post's hook program account")]
+    MissingHookProgram,
+    #[msg("Account is not in a legacy layout")]
+    NotLegacyAccount,
+    #[msg("Post index account is missing or not at the next post id")]
+    InvalidPostIndex,
+    #[msg("Post account is missing or not the one referred to")]
+    InvalidPostAccount,
+    #[msg("Poll account is missing or not the one voted on")]
+    InvalidPollAccount,
+    #[msg("Poll voted on still exists")]
+    PollExists,
+    #[msg("Missing revision account")]
+    MissingRevisionAccount,
+    #[msg("Invalid revision account")]
+    InvalidRevisionAccount,
+    #[msg("Payer of a revision is missing from the accounts")]
+    MissingRevisionPayer,
+    #[msg("Hook accounts may not include writable DePress accounts")]
+    HookAccountNotAllowed,
+    #[msg("Tip share would leave its recipient below the rent-exempt minimum")]
+    TipBelowRent,
 }
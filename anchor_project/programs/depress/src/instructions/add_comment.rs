@@ -4,12 +4,25 @@
 ///
 /// Requirements:
 /// - Enforce that `comment_content` does not exceed the maximum allowed length
+/// - Reject comments on scheduled posts before their publishing time
+/// - Reject comments on expired posts
 /// - Initialize a new `Comment` account as a PDA using seeds:
 ///   [COMMENT_SEED, parent_post pubkey, author pubkey, SHA256(content)]
 /// - Populate comment fields: content, author, parent post, and bump
 /// - Initialize engagement counters (likes/dislikes) to zero
 /// - Increment the parent post's `comment_count`
 /// - Emit a `CommentCreated` event for indexing
+/// - Store up to `MAX_MENTIONS` distinct mentioned wallets and emit a `Mentioned`
+///   event for each of them
+/// - Validate and store an optional media attachment
+/// - Validate and store the content labels (language, content warning, flags)
+/// - Accept an ephemeral key signing through a `Session` with `SESSION_COMMENT`
+///   in place of the wallet; the comment is authored by the session's wallet
+/// - Charge the rent to `payer`, which may differ from the author, and record it
+///   on the account so the rent goes back to it on close; rent paid by a session
+///   key goes back to its wallet
+/// - Invoke the post's hook program, if any, which may reject the comment; extra
+///   accounts for the hook are passed via `remaining_accounts`
 ///
 /// The content hash ensures comment uniqueness per author and post,
 /// preventing duplicate submissions while allowing multiple comments
@@ -19,57 +32,158 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash::hash;
 
-use crate::constants::{COMMENT_LENGTH, COMMENT_SEED, DISCRIMINATOR};
+use crate::constants::{
+    COMMENT_LENGTH, COMMENT_SEED, COMMENT_VERSION, CONFIG_SEED, DISCRIMINATOR, SESSION_COMMENT,
+    SESSION_SEED,
+};
 use crate::errors::DepressError;
-use crate::states::{Comment, Post};
+use crate::instructions::emit_mentions;
+use crate::states::{
+    Attachment, Comment, Config, ContentLabels, Engagement, EngagementKind, EventHeader, Post,
+    Session,
+};
+use crate::utils::{invoke_post_hook, validate_mentions, EventEmitter};
 
-pub fn add_comment(ctx: Context<AddCommentContext>, comment_content: String) -> Result<()> {
-    // Validate comment content length in bytes
-    require!(
-        comment_content.as_bytes().len() <= COMMENT_LENGTH,
-        DepressError::CommentTooLong
-    );
+pub fn add_comment<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddCommentContext<'info>>,
+    comment_content: String,
+    mentions: Vec<Pubkey>,
+    attachment: Option<Attachment>,
+    labels: ContentLabels,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    validate_comment(&comment_content, &ctx.accounts.post, now)?;
+    let comment_author = Session::authorize(
+        ctx.accounts.comment_author.key(),
+        ctx.accounts.session.as_deref(),
+        SESSION_COMMENT,
+        now,
+    )?;
+    validate_mentions(&comment_author, &mentions)?;
+    if let Some(attachment) = &attachment {
+        attachment.validate()?;
+    }
+    labels.validate()?;
 
     // Initialize the comment account
     let comment = &mut ctx.accounts.comment;
     let post = &mut ctx.accounts.post;
+    init_comment(
+        comment,
+        post,
+        comment_author,
+        Session::rent_payer(ctx.accounts.payer.key(), ctx.accounts.session.as_deref()),
+        comment_content,
+        ctx.bumps.comment,
+    );
+    comment.mentions = mentions;
+    comment.attachment = attachment;
+    comment.labels = labels;
 
-    comment.comment_author = ctx.accounts.comment_author.key();
-    comment.parent_post = post.key();
-    comment.content = comment_content.clone(); // Clone to use in event
-    comment.likes = 0;
-    comment.dislikes = 0;
-    comment.bump = ctx.bumps.comment;
+    invoke_post_hook(
+        post,
+        ctx.accounts.hook_program.as_deref(),
+        ctx.remaining_accounts,
+        &Engagement {
+            kind: EngagementKind::Comment,
+            post: post.key(),
+            actor: comment_author,
+            target: comment.key(),
+        },
+    )?;
 
-    // Increment comment count on the post
-    post.comment_count += 1;
+    emit_comment_created(
+        comment,
+        &mut EventEmitter::new(
+            ctx.accounts.config.as_deref_mut(),
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.bumps.event_authority,
+        ),
+    )
+}
 
-    // Emit event
-    emit!(CommentCreated {
-        author: comment.comment_author,
+/// Checks that `content` fits in a comment and that `post` accepts comments at `now`.
+pub(crate) fn validate_comment(content: &str, post: &Post, now: i64) -> Result<()> {
+    require!(
+        content.len() <= COMMENT_LENGTH,
+        DepressError::CommentTooLong
+    );
+    require!(post.is_published_at(now), DepressError::PostNotPublished);
+    require!(!post.is_expired_at(now), DepressError::PostExpired);
+
+    Ok(())
+}
+
+/// Initializes `comment` on `post` without mentions, attachment or labels, which
+/// the caller sets afterwards, and counts it on the post.
+pub(crate) fn init_comment(
+    comment: &mut Account<Comment>,
+    post: &mut Account<Post>,
+    comment_author: Pubkey,
+    payer: Pubkey,
+    content: String,
+    bump: u8,
+) {
+    comment.set_inner(Comment {
+        version: COMMENT_VERSION,
+        comment_author,
+        payer,
         parent_post: post.key(),
-        content: comment_content,
+        content,
+        mentions: Vec::new(),
+        attachment: None,
+        labels: ContentLabels::default(),
+        likes: 0,
+        dislikes: 0,
+        bump,
     });
+    post.comment_count += 1;
+}
 
-    Ok(())
+/// Emits `CommentCreated` for `comment`, followed by its `Mentioned` events.
+pub(crate) fn emit_comment_created(
+    comment: &Account<Comment>,
+    events: &mut EventEmitter,
+) -> Result<()> {
+    events.emit(|header| CommentCreated {
+        header,
+        author: comment.comment_author,
+        comment: comment.key(),
+        parent_post: comment.parent_post,
+        content: comment.content.clone(),
+        attachment: comment.attachment.clone(),
+        labels: comment.labels.clone(),
+    })?;
+    emit_mentions(
+        events,
+        comment.comment_author,
+        &comment.mentions,
+        comment.parent_post,
+        Some(comment.key()),
+    )
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(comment_content: String)]
 pub struct AddCommentContext<'info> {
-    #[account(mut)]
+    // The wallet, or a session key acting for it
     pub comment_author: Signer<'info>,
 
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(mut)]
     pub post: Account<'info, Post>,
 
     #[account(
         init,
-        payer = comment_author,
+        payer = payer,
         space = DISCRIMINATOR + Comment::INIT_SPACE,
         seeds = [
             COMMENT_SEED.as_bytes(),
-            comment_author.key().as_ref(),
+            &Session::author_seed(comment_author.key(), session.as_deref()),
             {hash(comment_content.as_bytes()).to_bytes().as_ref()},
             post.key().as_ref(),
         ],
@@ -78,11 +192,32 @@ pub struct AddCommentContext<'info> {
     pub comment: Account<'info, Comment>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            session.authority.as_ref(),
+            comment_author.key().as_ref(),
+        ],
+        bump = session.bump,
+    )]
+    pub session: Option<Account<'info, Session>>,
+
+    /// CHECK: Must match the post's `hook_program`, required when one is set.
+    pub hook_program: Option<UncheckedAccount<'info>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
 }
 
 #[event]
 pub struct CommentCreated {
+    pub header: EventHeader,
     pub author: Pubkey,
+    pub comment: Pubkey,
     pub parent_post: Pubkey,
     pub content: String,
+    pub attachment: Option<Attachment>,
+    pub labels: ContentLabels,
 }
@@ -0,0 +1,114 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "add poll" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may attach a poll to their post
+/// - Enforce between `MIN_POLL_OPTIONS` and `MAX_POLL_OPTIONS` options, each at
+///   most `POLL_OPTION_LENGTH` bytes
+/// - Enforce that an optional closing time lies in the future
+/// - Initialize a new `Poll` account as a PDA using seeds: [POLL_SEED, post pubkey]
+/// - Initialize one zeroed tally per option and mark the post as having a poll
+/// - Emit a `PollCreated` event for off-chain indexing
+/// - Charge the rent to the post's `payer`, which gets it back when the post is
+///   removed
+///
+/// The PDA design allows a single poll per post. The poll is closed together
+/// with its parent post in `post_remove`.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CONFIG_SEED, DISCRIMINATOR, MAX_POLL_OPTIONS, MIN_POLL_OPTIONS, POLL_OPTION_LENGTH, POLL_SEED,
+    POLL_VERSION,
+};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Poll, Post};
+
+pub fn add_poll(
+    ctx: Context<AddPollContext>,
+    options: Vec<String>,
+    closes_at: Option<i64>,
+) -> Result<()> {
+    // Validate options
+    require!(
+        (MIN_POLL_OPTIONS..=MAX_POLL_OPTIONS).contains(&options.len()),
+        DepressError::InvalidPollOptions
+    );
+    for option in &options {
+        require!(
+            !option.is_empty() && option.len() <= POLL_OPTION_LENGTH,
+            DepressError::PollOptionTooLong
+        );
+    }
+
+    // Validate closing time
+    if let Some(closes_at) = closes_at {
+        require!(
+            closes_at > Clock::get()?.unix_timestamp,
+            DepressError::InvalidPollClosingTime
+        );
+    }
+
+    // Initialize the poll account
+    let poll = &mut ctx.accounts.poll;
+    poll.version = POLL_VERSION;
+    poll.parent_post = ctx.accounts.post.key();
+    poll.tallies = vec![0; options.len()];
+    poll.options = options.clone();
+    poll.closes_at = closes_at;
+    poll.bump = ctx.bumps.poll;
+
+    ctx.accounts.post.has_poll = true;
+
+    // Emit event
+    emit_cpi!(PollCreated {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        poll: poll.key(),
+        parent_post: poll.parent_post,
+        options,
+        closes_at,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddPollContext<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + Poll::INIT_SPACE,
+        seeds = [
+            POLL_SEED.as_bytes(),
+            post.key().as_ref()
+        ],
+        bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Must be the post's payer, which gets the rent back when the post is removed
+    #[account(mut, address = post.payer @ DepressError::InvalidPayer)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PollCreated {
+    pub header: EventHeader,
+    pub poll: Pubkey,
+    pub parent_post: Pubkey,
+    pub options: Vec<String>,
+    pub closes_at: Option<i64>,
+}
@@ -8,6 +8,39 @@
 /// - Populate the post with the provided topic, content, and author
 /// - Initialize engagement counters (likes, dislikes, comments) to zero
 /// - Store the PDA bump for future rederivation
+/// - Extract up to `MAX_HASHTAGS` distinct `#hashtags` from the content and store them
+/// - Record the post in the `Tag` index account of every hashtag, passed in order via
+///   `remaining_accounts` as [TAG_SEED, tag] PDAs (created on first use)
+/// - Store up to `MAX_MENTIONS` distinct mentioned wallets and emit a `Mentioned`
+///   event for each of them
+/// - When a `content_ref` is given, validate it and require the inline content to be
+///   empty: the post body then lives off-chain, committed to by its SHA-256 hash
+/// - Validate and store up to `MAX_POST_ATTACHMENTS` media attachments
+/// - Validate and store the content labels (language, content warning, flags)
+/// - When `publish_at` is given, it must lie in the future: the post is then
+///   scheduled and rejects comments and reactions until `publish_due` runs
+/// - When `expires_at` is given, it must lie in the future and after `publish_at`:
+///   the post then turns read-only once it passes and may be closed by anyone
+///   through `close_expired_post`
+/// - When the optional `quoted_post` account is provided, it must be published and
+///   not expired; reference it as the quoted post and emit a `QuotePostCreated` event
+/// - When `draft` is set, store the post as a draft instead: hashtags are not
+///   indexed, no `PostCreated`, `Mentioned` or `QuotePostCreated` events are
+///   emitted and a `DraftSaved` event is emitted in their place; comments and
+///   reactions are rejected until `draft_publish` runs
+/// - Store up to `MAX_CO_AUTHORS` distinct co-authors with their share of tips;
+///   co-authors signing the transaction (passed as signers in `remaining_accounts`,
+///   after the tag accounts) approve the post right away, the others through
+///   `co_author_approve`; comments and reactions are rejected until every
+///   co-author approved
+/// - Accept an ephemeral key signing through a `Session` with `SESSION_POST` in
+///   place of the wallet; the post is then authored by the session's wallet
+/// - Charge the rent to `payer`, which may differ from the author, and record it
+///   on the account so the rent goes back to it on close; rent paid by a session
+///   key is recorded for, and refunded to, the session's wallet
+/// - Take the next id of the global post sequence from the `Config` account as the
+///   post's `global_id`, and create its `PostIndex` PDA using the seeds:
+///   [INDEX_SEED, global_id] pointing at the post
 ///
 /// The `Post` account is owned by the program and funded by the post author.
 /// The topic is part of the PDA seed to allow multiple posts per user under different topics.
@@ -15,71 +48,377 @@
 //-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
 
-use crate::constants::{CONTENT_LENGTH, DISCRIMINATOR, POST_SEED, TOPIC_LENGTH};
+use crate::constants::{
+    CONFIG_SEED, CONTENT_LENGTH, DISCRIMINATOR, INDEX_SEED, INDEX_VERSION, MAX_POST_ATTACHMENTS,
+    POST_SEED, POST_VERSION, SESSION_POST, SESSION_SEED, TOPIC_LENGTH,
+};
 use crate::errors::DepressError;
-use crate::states::Post;
+use crate::states::{
+    Attachment, CoAuthor, Config, ContentLabels, ContentRef, EventHeader, Post, PostIndex, Session,
+    Tag,
+};
+use crate::utils::{index_hashtags, validate_co_authors, validate_mentions, EventEmitter};
 
-pub fn add_post(
-    ctx: Context<AddPostContext>,
+#[allow(clippy::too_many_arguments)]
+pub fn add_post<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddPostContext<'info>>,
     post_topic: String,
     post_content: String,
+    mentions: Vec<Pubkey>,
+    content_ref: Option<ContentRef>,
+    attachments: Vec<Attachment>,
+    labels: ContentLabels,
+    publish_at: Option<i64>,
+    expires_at: Option<i64>,
+    draft: bool,
+    co_authors: Vec<CoAuthor>,
 ) -> Result<()> {
     // Validate topic and content lengths
+    let hashtags = validate_post_text(&post_topic, &post_content)?;
+
+    if let Some(content_ref) = &content_ref {
+        require!(
+            post_content.is_empty(),
+            DepressError::InlineContentNotAllowed
+        );
+        content_ref.validate()?;
+    }
+
     require!(
-        post_topic.as_bytes().len() <= TOPIC_LENGTH,
-        DepressError::TopicTooLong
+        attachments.len() <= MAX_POST_ATTACHMENTS,
+        DepressError::TooManyAttachments
     );
+    for attachment in &attachments {
+        attachment.validate()?;
+    }
+
+    labels.validate()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(quoted_post) = &ctx.accounts.quoted_post {
+        require!(
+            quoted_post.is_published_at(now),
+            DepressError::PostNotPublished
+        );
+        require!(!quoted_post.is_expired_at(now), DepressError::PostExpired);
+    }
+    if let Some(publish_at) = publish_at {
+        require!(publish_at > now, DepressError::InvalidPublishTime);
+    }
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > publish_at.unwrap_or(now),
+            DepressError::InvalidExpiryTime
+        );
+    }
+
+    let post_author = Session::authorize(
+        ctx.accounts.post_author.key(),
+        ctx.accounts.session.as_deref(),
+        SESSION_POST,
+        now,
+    )?;
+    validate_mentions(&post_author, &mentions)?;
+    validate_co_authors(&post_author, &co_authors)?;
+
+    // Co-authors signing the post approve it right away
+    let co_authors: Vec<CoAuthor> = co_authors
+        .into_iter()
+        .map(|co_author| CoAuthor {
+            approved: ctx
+                .remaining_accounts
+                .iter()
+                .any(|account| account.is_signer && account.key() == co_author.author),
+            ..co_author
+        })
+        .collect();
+
+    // Initialize the post account
+    let post = &mut ctx.accounts.post;
+    init_post(
+        post,
+        post_author,
+        Session::rent_payer(ctx.accounts.payer.key(), ctx.accounts.session.as_deref()),
+        post_topic,
+        post_content,
+        hashtags,
+        ctx.bumps.post,
+    );
+    post.content_ref = content_ref;
+    post.attachments = attachments;
+    post.labels = labels;
+    post.mentions = mentions;
+    post.quoted_post = ctx.accounts.quoted_post.as_ref().map(|quoted| quoted.key());
+    post.publish_at = publish_at;
+    post.expires_at = expires_at;
+    post.is_draft = draft;
+    post.co_authors = co_authors;
+    post.published = post.is_published_at(now);
+
+    // Index the post in the global sequence
+    index_post(
+        post,
+        &mut ctx.accounts.post_index,
+        ctx.bumps.post_index,
+        &mut ctx.accounts.config,
+    );
+
+    // Drafts stay out of the tag index and the public event stream
+    if draft {
+        emit_cpi!(DraftSaved {
+            header: ctx.accounts.config.next_header()?,
+            post_author: post.post_author,
+            post: post.key(),
+            topic: post.topic.clone(),
+            saved_at: now,
+        });
+        return Ok(());
+    }
+
+    announce_post(
+        post,
+        ctx.remaining_accounts,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &mut EventEmitter::new(
+            Some(&mut ctx.accounts.config),
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.bumps.event_authority,
+        ),
+    )
+}
 
+/// Checks the topic and inline content lengths of a new post and returns the
+/// hashtags of its content.
+pub(crate) fn validate_post_text(topic: &str, content: &str) -> Result<Vec<String>> {
+    require!(topic.len() <= TOPIC_LENGTH, DepressError::TopicTooLong);
     require!(
-        post_content.as_bytes().len() <= CONTENT_LENGTH,
+        content.len() <= CONTENT_LENGTH,
         DepressError::ContentTooLong
     );
 
-    // Initialize the post account
-    let post = &mut ctx.accounts.post;
-    post.post_author = ctx.accounts.post_author.key();
+    Tag::parse_hashtags(content)
+}
 
-    post.topic = post_topic.clone();
-    post.content = post_content;
+/// Initializes `post` as a published post by `post_author` without engagement,
+/// off-chain content, attachments, labels, mentions, schedule or co-authors; the
+/// caller sets those afterwards.
+pub(crate) fn init_post(
+    post: &mut Account<Post>,
+    post_author: Pubkey,
+    payer: Pubkey,
+    topic: String,
+    content: String,
+    hashtags: Vec<String>,
+    bump: u8,
+) {
+    post.set_inner(Post {
+        version: POST_VERSION,
+        post_author,
+        payer,
+        topic,
+        total_length: content.len() as u32,
+        content,
+        content_ref: None,
+        attachments: Vec::new(),
+        labels: ContentLabels::default(),
+        hashtags,
+        mentions: Vec::new(),
+        likes: 0,
+        dislikes: 0,
+        comment_count: 0,
+        repost_count: 0,
+        quoted_post: None,
+        has_poll: false,
+        is_pinned: false,
+        revision_count: 0,
+        chunk_count: 0,
+        finalized: false,
+        publish_at: None,
+        published: true,
+        expires_at: None,
+        is_draft: false,
+        co_authors: Vec::new(),
+        removal_votes: 0,
+        hook_program: None,
+        global_id: 0,
+        bump,
+    });
+}
 
-    post.likes = 0;
-    post.dislikes = 0;
-    post.comment_count = 0;
+/// Gives `post` the next `global_id` of `config` and points its `PostIndex` at it.
+pub(crate) fn index_post(
+    post: &mut Account<Post>,
+    post_index: &mut Account<PostIndex>,
+    index_bump: u8,
+    config: &mut Config,
+) {
+    post.global_id = config.next_post_id();
+    post_index.set_inner(PostIndex {
+        version: INDEX_VERSION,
+        post: post.key(),
+        bump: index_bump,
+    });
+}
 
-    post.bump = ctx.bumps.post;
+/// Makes a post public: records it in the `Tag` accounts of its hashtags, passed
+/// in order as `tag_accounts` and created on first use, and emits its creation
+/// events.
+pub(crate) fn announce_post<'info>(
+    post: &Account<'info, Post>,
+    tag_accounts: &'info [AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    events: &mut EventEmitter,
+) -> Result<()> {
+    index_hashtags(
+        &post.hashtags,
+        tag_accounts,
+        post.key(),
+        payer,
+        system_program,
+    )?;
+    emit_post_created(post, events)
+}
 
-    // Emit event
-    emit!(PostCreated {
+/// Emits `PostCreated` for `post`, followed by its `Mentioned` and
+/// `QuotePostCreated` events.
+fn emit_post_created(post: &Account<Post>, events: &mut EventEmitter) -> Result<()> {
+    events.emit(|header| PostCreated {
+        header,
         post_author: post.post_author,
-        topic: post_topic,
-    });
+        post: post.key(),
+        topic: post.topic.clone(),
+        hashtags: post.hashtags.clone(),
+        attachments: post.attachments.clone(),
+        labels: post.labels.clone(),
+        publish_at: post.publish_at,
+        expires_at: post.expires_at,
+        co_authors: post.co_authors.clone(),
+    })?;
+    emit_mentions(events, post.post_author, &post.mentions, post.key(), None)?;
+    if let Some(quoted_post) = post.quoted_post {
+        events.emit(|header| QuotePostCreated {
+            header,
+            post_author: post.post_author,
+            post: post.key(),
+            quoted_post,
+        })?;
+    }
 
     Ok(())
 }
 
+/// Emits one `Mentioned` event per mentioned wallet.
+pub(crate) fn emit_mentions(
+    events: &mut EventEmitter,
+    author: Pubkey,
+    mentions: &[Pubkey],
+    post: Pubkey,
+    comment: Option<Pubkey>,
+) -> Result<()> {
+    for mentioned in mentions {
+        events.emit(|header| Mentioned {
+            header,
+            mentioned: *mentioned,
+            author,
+            post,
+            comment,
+        })?;
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(post_topic: String)]
 pub struct AddPostContext<'info> {
     #[account(
         init,
-        payer = post_author,
+        payer = payer,
         space = DISCRIMINATOR + Post::INIT_SPACE,
         seeds = [
             POST_SEED.as_bytes(),
             post_topic.as_bytes(),
-            post_author.key().as_ref()
+            &Session::author_seed(post_author.key(), session.as_deref())
         ],
         bump
     )]
     pub post: Account<'info, Post>,
 
-    #[account(mut)]
+    // The wallet, or a session key acting for it
     pub post_author: Signer<'info>,
+
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    pub quoted_post: Option<Account<'info, Post>>,
+
+    #[account(
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            session.authority.as_ref(),
+            post_author.key().as_ref(),
+        ],
+        bump = session.bump,
+    )]
+    pub session: Option<Account<'info, Session>>,
+
+    // Numbers the emitted events
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Entry of the post in the global sequence
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + PostIndex::INIT_SPACE,
+        seeds = [INDEX_SEED.as_bytes(), config.post_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub post_index: Account<'info, PostIndex>,
 }
 
 #[event]
 pub struct PostCreated {
+    pub header: EventHeader,
+    pub post_author: Pubkey,
+    pub post: Pubkey,
+    pub topic: String,
+    pub hashtags: Vec<String>,
+    pub attachments: Vec<Attachment>,
+    pub labels: ContentLabels,
+    pub publish_at: Option<i64>,
+    pub expires_at: Option<i64>,
+    pub co_authors: Vec<CoAuthor>,
+}
+
+#[event]
+pub struct DraftSaved {
+    pub header: EventHeader,
     pub post_author: Pubkey,
+    pub post: Pubkey,
     pub topic: String,
+    pub saved_at: i64,
+}
+
+#[event]
+pub struct QuotePostCreated {
+    pub header: EventHeader,
+    pub post_author: Pubkey,
+    pub post: Pubkey,
+    pub quoted_post: Pubkey,
+}
+
+#[event]
+pub struct Mentioned {
+    pub header: EventHeader,
+    pub mentioned: Pubkey,
+    pub author: Pubkey,
+    pub post: Pubkey,
+    // Set when the mention comes from a comment on `post`
+    pub comment: Option<Pubkey>,
 }
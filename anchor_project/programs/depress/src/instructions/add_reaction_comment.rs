@@ -3,12 +3,20 @@
 /// TASK: Implement the "add reaction to comment" functionality for the DePress program
 ///
 /// Requirements:
+/// - Reject reactions on comments of expired posts
+/// - Accept an ephemeral key signing through a `Session` with `SESSION_REACT`
+///   in place of the wallet; the reaction is recorded for the session's wallet
 /// - Initialize a new `ReactionComment` account as a PDA using seeds:
 ///   [COMMENT_REACTION_SEED, author pubkey, parent comment pubkey]
 /// - Set reaction fields: type (Like/Dislike), author, parent comment, and bump
 /// - Increment the corresponding counter (`likes` or `dislikes`) on the parent comment
 /// - Prevent duplicate reactions via PDA uniqueness (one reaction per user per comment)
 /// - Emit a `ReactionAdded` event for indexing and real-time updates
+/// - Charge the rent to `payer`, which may differ from the author, and record it
+///   on the account so the rent goes back to it on close; a session key paying
+///   records its wallet instead
+/// - Invoke the post's hook program, if any, which may reject the reaction; extra
+///   accounts for the hook are passed via `remaining_accounts`
 ///
 /// Note: The PDA design ensures a user can only react once per comment.
 /// To support toggling or updating reactions, additional logic would be needed.
@@ -16,20 +24,41 @@
 //-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
 
-use crate::constants::{COMMENT_REACTION_SEED, DISCRIMINATOR};
-use crate::states::{Comment, ReactionComment, ReactionType};
+use crate::constants::{
+    COMMENT_REACTION_SEED, CONFIG_SEED, DISCRIMINATOR, REACTION_VERSION, SESSION_REACT,
+    SESSION_SEED,
+};
+use crate::errors::DepressError;
+use crate::states::{
+    Comment, Config, Engagement, EngagementKind, EventHeader, Post, ReactionComment, ReactionType,
+    Session,
+};
+use crate::utils::invoke_post_hook;
 
-pub fn add_reaction_comment(
-    ctx: Context<AddReactionCommentContext>,
+pub fn add_reaction_comment<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddReactionCommentContext<'info>>,
     reaction: ReactionType,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.post.is_expired_at(now),
+        DepressError::PostExpired
+    );
+    let reaction_author = Session::authorize(
+        ctx.accounts.reaction_author.key(),
+        ctx.accounts.session.as_deref(),
+        SESSION_REACT,
+        now,
+    )?;
     let comment_reaction = &mut ctx.accounts.comment_reaction;
     let comment = &mut ctx.accounts.comment;
-    let reaction_author = ctx.accounts.reaction_author.key();
     let reaction_clone = reaction.clone();
 
     // Initialize reaction account
+    comment_reaction.version = REACTION_VERSION;
     comment_reaction.reaction_author = reaction_author;
+    comment_reaction.payer =
+        Session::rent_payer(ctx.accounts.payer.key(), ctx.accounts.session.as_deref());
     comment_reaction.parent_comment = comment.key();
     comment_reaction.reaction = reaction;
     comment_reaction.bump = ctx.bumps.comment_reaction;
@@ -40,47 +69,86 @@ pub fn add_reaction_comment(
         ReactionType::Dislike => comment.dislikes += 1,
     }
 
+    invoke_post_hook(
+        &ctx.accounts.post,
+        ctx.accounts.hook_program.as_deref(),
+        ctx.remaining_accounts,
+        &Engagement {
+            kind: EngagementKind::CommentReaction(reaction_clone.clone()),
+            post: ctx.accounts.post.key(),
+            actor: reaction_author,
+            target: comment_reaction.key(),
+        },
+    )?;
+
     // Emit event
-    emit!(ReactionCommentAdded {
+    emit_cpi!(ReactionCommentAdded {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
         author: reaction_author,
         parent_comment: comment.key(),
-        reaction: match reaction_clone {
-            ReactionType::Like => 0,
-            ReactionType::Dislike => 1,
-        },
+        parent_post: comment.parent_post,
+        reaction: reaction_clone,
     });
 
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct AddReactionCommentContext<'info> {
     #[account(
         init,
-        payer = reaction_author,
+        payer = payer,
         space = DISCRIMINATOR + ReactionComment::INIT_SPACE,
         seeds = [
             COMMENT_REACTION_SEED.as_bytes(),
-            reaction_author.key().as_ref(),
+            &Session::author_seed(reaction_author.key(), session.as_deref()),
             comment.key().as_ref(),
         ],
         bump
     )]
     pub comment_reaction: Account<'info, ReactionComment>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = comment.parent_post == post.key() @ DepressError::InvalidParentPost,
+    )]
     pub comment: Account<'info, Comment>,
 
-    #[account(mut)]
+    pub post: Account<'info, Post>,
+
+    // The wallet, or a session key acting for it
     pub reaction_author: Signer<'info>,
 
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            session.authority.as_ref(),
+            reaction_author.key().as_ref(),
+        ],
+        bump = session.bump,
+    )]
+    pub session: Option<Account<'info, Session>>,
+
+    /// CHECK: Must match the post's `hook_program`, required when one is set.
+    pub hook_program: Option<UncheckedAccount<'info>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
 }
 
 #[event]
 pub struct ReactionCommentAdded {
+    pub header: EventHeader,
     pub author: Pubkey,
     pub parent_comment: Pubkey,
-    // 0 = Like, 1 = Dislike
-    pub reaction: u8,
+    pub parent_post: Pubkey,
+    pub reaction: ReactionType,
 }
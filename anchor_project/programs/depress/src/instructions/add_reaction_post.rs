@@ -3,12 +3,21 @@
 /// TASK: Implement the "add reaction to post" functionality for the DePress program
 ///
 /// Requirements:
+/// - Reject reactions on scheduled posts before their publishing time
+/// - Reject reactions on expired posts
+/// - Accept an ephemeral key signing through a `Session` with `SESSION_REACT`
+///   in place of the wallet; the reaction is recorded for the session's wallet
 /// - Initialize a new `ReactionPost` account as a PDA using seeds:
 ///   [POST_REACTION_SEED, author pubkey, parent post pubkey]
 /// - Set reaction fields: type (Like/Dislike), author, parent post, and bump
 /// - Increment the corresponding counter (`likes` or `dislikes`) on the parent post
 /// - Prevent duplicate reactions via PDA uniqueness (one per user per post)
 /// - Emit a `ReactionPostAdded` event for off-chain indexing
+/// - Charge the rent to `payer`, which may differ from the author, and record it
+///   on the account so the rent goes back to it on close; a session key paying
+///   records its wallet instead
+/// - Invoke the post's hook program, if any, which may reject the reaction; extra
+///   accounts for the hook are passed via `remaining_accounts`
 ///
 /// Note: The PDA design ensures a user can only react once. To support toggling,
 /// a separate remove/toggle instruction is needed.
@@ -16,21 +25,38 @@
 //-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
 
-use crate::constants::{DISCRIMINATOR, POST_REACTION_SEED};
-use crate::states::{Post, ReactionPost, ReactionType};
+use crate::constants::{
+    CONFIG_SEED, DISCRIMINATOR, POST_REACTION_SEED, REACTION_VERSION, SESSION_REACT, SESSION_SEED,
+};
+use crate::errors::DepressError;
+use crate::states::{
+    Config, Engagement, EngagementKind, EventHeader, Post, ReactionPost, ReactionType, Session,
+};
+use crate::utils::invoke_post_hook;
 
-pub fn add_reaction_post(
-    ctx: Context<AddReactionPostContext>,
+pub fn add_reaction_post<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddReactionPostContext<'info>>,
     reaction: ReactionType,
 ) -> Result<()> {
     // Clone the reaction to use it in the match later
     let post = &mut ctx.accounts.post;
-    let reaction_author = ctx.accounts.reaction_author.key();
+    let now = Clock::get()?.unix_timestamp;
+    require!(post.is_published_at(now), DepressError::PostNotPublished);
+    require!(!post.is_expired_at(now), DepressError::PostExpired);
+    let reaction_author = Session::authorize(
+        ctx.accounts.reaction_author.key(),
+        ctx.accounts.session.as_deref(),
+        SESSION_REACT,
+        now,
+    )?;
     let reaction_clone = reaction.clone();
 
     // Initialize the reaction account
     let post_reaction = &mut ctx.accounts.post_reaction;
+    post_reaction.version = REACTION_VERSION;
     post_reaction.reaction_author = reaction_author;
+    post_reaction.payer =
+        Session::rent_payer(ctx.accounts.payer.key(), ctx.accounts.session.as_deref());
     post_reaction.parent_post = post.key();
     post_reaction.reaction = reaction;
     post_reaction.bump = ctx.bumps.post_reaction;
@@ -42,27 +68,38 @@ pub fn add_reaction_post(
         ReactionType::Dislike => post.dislikes += 1,
     }
 
+    invoke_post_hook(
+        post,
+        ctx.accounts.hook_program.as_deref(),
+        ctx.remaining_accounts,
+        &Engagement {
+            kind: EngagementKind::PostReaction(reaction_clone.clone()),
+            post: post.key(),
+            actor: reaction_author,
+            target: post_reaction.key(),
+        },
+    )?;
+
     // Emit event
-    emit!(ReactionPostAdded {
-        author: ctx.accounts.reaction_author.key(),
+    emit_cpi!(ReactionPostAdded {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        author: reaction_author,
         parent_post: ctx.accounts.post.key(),
-        reaction: match reaction_clone {
-            ReactionType::Like => 0,
-            ReactionType::Dislike => 1,
-        },
+        reaction: reaction_clone,
     });
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct AddReactionPostContext<'info> {
     #[account(
         init,
-        payer = reaction_author,
+        payer = payer,
         space = DISCRIMINATOR + ReactionPost::INIT_SPACE,
         seeds = [
             POST_REACTION_SEED.as_bytes(),
-            reaction_author.key().as_ref(),
+            &Session::author_seed(reaction_author.key(), session.as_deref()),
             post.key().as_ref()
         ],
         bump
@@ -72,15 +109,36 @@ pub struct AddReactionPostContext<'info> {
     #[account(mut)]
     pub post: Account<'info, Post>,
 
-    #[account(mut)]
+    // The wallet, or a session key acting for it
     pub reaction_author: Signer<'info>,
+
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            session.authority.as_ref(),
+            reaction_author.key().as_ref(),
+        ],
+        bump = session.bump,
+    )]
+    pub session: Option<Account<'info, Session>>,
+
+    /// CHECK: Must match the post's `hook_program`, required when one is set.
+    pub hook_program: Option<UncheckedAccount<'info>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
 }
 
 #[event]
 pub struct ReactionPostAdded {
+    pub header: EventHeader,
     pub author: Pubkey,
     pub parent_post: Pubkey,
-    // 0 = Like, 1 = Dislike
-    pub reaction: u8,
+    pub reaction: ReactionType,
 }
@@ -0,0 +1,152 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "add relayed comment" functionality for the DePress program
+///
+/// Requirements:
+/// - Any relayer may submit a comment the author signed off-chain; the relayer
+///   signs the transaction and pays for the accounts as `payer`
+/// - Require the instruction right before this one to be an Ed25519 program
+///   instruction verifying the author's signature over the `RelayedMessage::Comment`
+///   built from this program, the nonce, the parent post, its `global_id` and the
+///   content
+/// - Require `nonce` to match the author's `AuthorNonce` account, created on first
+///   use as a PDA using seeds: [NONCE_SEED, author pubkey], and increment it
+/// - Reject comments on posts that are not published or have expired
+/// - Create the `Comment` with the verified author, using the seeds
+///   [COMMENT_SEED, author pubkey, SHA256(content), parent_post pubkey]
+/// - Increment the parent post's `comment_count` and emit a `CommentCreated` event
+/// - Invoke the post's hook program, if any, which may reject the comment; extra
+///   accounts for the hook are passed via `remaining_accounts`
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::constants::{COMMENT_SEED, CONFIG_SEED, DISCRIMINATOR, NONCE_SEED, NONCE_VERSION};
+use crate::errors::DepressError;
+use crate::instructions::{emit_comment_created, init_comment, validate_comment};
+use crate::states::{
+    AuthorNonce, Comment, Config, Engagement, EngagementKind, Post, RelayedMessage,
+};
+use crate::utils::{invoke_post_hook, verify_ed25519_instruction, EventEmitter};
+
+pub fn add_relayed_comment<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddRelayedCommentContext<'info>>,
+    comment_content: String,
+    nonce: u64,
+) -> Result<()> {
+    validate_comment(
+        &comment_content,
+        &ctx.accounts.post,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Verify the author's off-chain signature
+    let comment_author = ctx.accounts.comment_author.key();
+    let message = RelayedMessage::Comment {
+        program_id: crate::ID,
+        nonce,
+        parent_post: ctx.accounts.post.key(),
+        parent_global_id: ctx.accounts.post.global_id,
+        content: comment_content.clone(),
+    };
+    verify_ed25519_instruction(
+        &ctx.accounts.instructions,
+        &comment_author,
+        &message.to_bytes()?,
+    )?;
+
+    let author_nonce = &mut ctx.accounts.author_nonce;
+    require!(nonce == author_nonce.nonce, DepressError::InvalidRelayNonce);
+    author_nonce.version = NONCE_VERSION;
+    author_nonce.author = comment_author;
+    author_nonce.nonce += 1;
+    author_nonce.bump = ctx.bumps.author_nonce;
+
+    // Initialize the comment account
+    let comment = &mut ctx.accounts.comment;
+    let post = &mut ctx.accounts.post;
+    init_comment(
+        comment,
+        post,
+        comment_author,
+        ctx.accounts.payer.key(),
+        comment_content,
+        ctx.bumps.comment,
+    );
+
+    invoke_post_hook(
+        post,
+        ctx.accounts.hook_program.as_deref(),
+        ctx.remaining_accounts,
+        &Engagement {
+            kind: EngagementKind::Comment,
+            post: post.key(),
+            actor: comment_author,
+            target: comment.key(),
+        },
+    )?;
+
+    emit_comment_created(
+        comment,
+        &mut EventEmitter::new(
+            ctx.accounts.config.as_deref_mut(),
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.bumps.event_authority,
+        ),
+    )
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(comment_content: String)]
+pub struct AddRelayedCommentContext<'info> {
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + Comment::INIT_SPACE,
+        seeds = [
+            COMMENT_SEED.as_bytes(),
+            comment_author.key().as_ref(),
+            {hash(comment_content.as_bytes()).to_bytes().as_ref()},
+            post.key().as_ref(),
+        ],
+        bump
+    )]
+    pub comment: Account<'info, Comment>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR + AuthorNonce::INIT_SPACE,
+        seeds = [
+            NONCE_SEED.as_bytes(),
+            comment_author.key().as_ref()
+        ],
+        bump
+    )]
+    pub author_nonce: Account<'info, AuthorNonce>,
+
+    /// CHECK: Authenticated by the Ed25519 signature over the relayed message.
+    pub comment_author: UncheckedAccount<'info>,
+
+    // The relayer, funds the new accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The instructions sysvar, checked by address.
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Must match the post's `hook_program`, required when one is set.
+    pub hook_program: Option<UncheckedAccount<'info>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
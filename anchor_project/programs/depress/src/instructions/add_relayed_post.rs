@@ -0,0 +1,149 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "add relayed post" functionality for the DePress program
+///
+/// Requirements:
+/// - Any relayer may submit a post the author signed off-chain; the relayer signs
+///   the transaction and pays for the accounts as `payer`
+/// - Require the instruction right before this one to be an Ed25519 program
+///   instruction verifying the author's signature over the `RelayedMessage::Post`
+///   built from this program, the nonce, the topic and the content
+/// - Require `nonce` to match the author's `AuthorNonce` account, created on first
+///   use as a PDA using seeds: [NONCE_SEED, author pubkey], and increment it
+/// - Enforce the usual topic and content lengths and create the `Post` with the
+///   verified author, using the seeds [POST_SEED, topic, author pubkey]
+/// - Record the post in the `Tag` index account of every hashtag, passed in order
+///   via `remaining_accounts`
+/// - Take the post's `global_id` and create its `PostIndex` like `add_post` does
+/// - Emit a `PostCreated` event
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CONFIG_SEED, DISCRIMINATOR, INDEX_SEED, NONCE_SEED, NONCE_VERSION, POST_SEED,
+};
+use crate::errors::DepressError;
+use crate::instructions::{announce_post, index_post, init_post, validate_post_text};
+use crate::states::{AuthorNonce, Config, Post, PostIndex, RelayedMessage};
+use crate::utils::{verify_ed25519_instruction, EventEmitter};
+
+pub fn add_relayed_post<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddRelayedPostContext<'info>>,
+    post_topic: String,
+    post_content: String,
+    nonce: u64,
+) -> Result<()> {
+    // Validate topic and content lengths
+    let hashtags = validate_post_text(&post_topic, &post_content)?;
+
+    // Verify the author's off-chain signature
+    let post_author = ctx.accounts.post_author.key();
+    let message = RelayedMessage::Post {
+        program_id: crate::ID,
+        nonce,
+        topic: post_topic.clone(),
+        content: post_content.clone(),
+    };
+    verify_ed25519_instruction(
+        &ctx.accounts.instructions,
+        &post_author,
+        &message.to_bytes()?,
+    )?;
+
+    let author_nonce = &mut ctx.accounts.author_nonce;
+    require!(nonce == author_nonce.nonce, DepressError::InvalidRelayNonce);
+    author_nonce.version = NONCE_VERSION;
+    author_nonce.author = post_author;
+    author_nonce.nonce += 1;
+    author_nonce.bump = ctx.bumps.author_nonce;
+
+    // Initialize the post account
+    let post = &mut ctx.accounts.post;
+    init_post(
+        post,
+        post_author,
+        ctx.accounts.payer.key(),
+        post_topic,
+        post_content,
+        hashtags,
+        ctx.bumps.post,
+    );
+
+    // Index the post in the global sequence
+    index_post(
+        post,
+        &mut ctx.accounts.post_index,
+        ctx.bumps.post_index,
+        &mut ctx.accounts.config,
+    );
+
+    announce_post(
+        post,
+        ctx.remaining_accounts,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &mut EventEmitter::new(
+            Some(&mut ctx.accounts.config),
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.bumps.event_authority,
+        ),
+    )
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(post_topic: String)]
+pub struct AddRelayedPostContext<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + Post::INIT_SPACE,
+        seeds = [
+            POST_SEED.as_bytes(),
+            post_topic.as_bytes(),
+            post_author.key().as_ref()
+        ],
+        bump
+    )]
+    pub post: Account<'info, Post>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR + AuthorNonce::INIT_SPACE,
+        seeds = [
+            NONCE_SEED.as_bytes(),
+            post_author.key().as_ref()
+        ],
+        bump
+    )]
+    pub author_nonce: Account<'info, AuthorNonce>,
+
+    /// CHECK: Authenticated by the Ed25519 signature over the relayed message.
+    pub post_author: UncheckedAccount<'info>,
+
+    // The relayer, funds the new accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The instructions sysvar, checked by address.
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Entry of the post in the global sequence
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + PostIndex::INIT_SPACE,
+        seeds = [INDEX_SEED.as_bytes(), config.post_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub post_index: Account<'info, PostIndex>,
+}
@@ -0,0 +1,94 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "repost" functionality for the DePress program
+///
+/// Requirements:
+/// - Only reposts published posts that have not expired
+/// - Initialize a new `Repost` account as a PDA using seeds:
+///   [REPOST_SEED, reposter pubkey, original post pubkey]
+/// - Set repost fields: reposter, original post, its `global_id`, and bump
+/// - Increment the `repost_count` on the original post
+/// - Prevent duplicate reposts via PDA uniqueness (one per user per post)
+/// - Emit a `RepostCreated` event for off-chain indexing
+/// - Charge the rent to `payer`, which may differ from the reposter, and record it
+///   on the account so the rent goes back to it on close
+///
+/// Quote posts are regular posts created through `post_add` with the quoted
+/// post passed as the optional `quoted_post` account.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, DISCRIMINATOR, REPOST_SEED, REPOST_VERSION};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post, Repost};
+
+pub fn add_repost(ctx: Context<AddRepostContext>) -> Result<()> {
+    let original_post = &mut ctx.accounts.original_post;
+    let reposter = ctx.accounts.reposter.key();
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        original_post.is_published_at(now),
+        DepressError::PostNotPublished
+    );
+    require!(!original_post.is_expired_at(now), DepressError::PostExpired);
+
+    // Initialize the repost account
+    let repost = &mut ctx.accounts.repost;
+    repost.version = REPOST_VERSION;
+    repost.reposter = reposter;
+    repost.payer = ctx.accounts.payer.key();
+    repost.original_post = original_post.key();
+    repost.original_global_id = original_post.global_id;
+    repost.bump = ctx.bumps.repost;
+
+    // Update the original post's repost count
+    original_post.repost_count += 1;
+
+    // Emit event
+    emit_cpi!(RepostCreated {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        reposter,
+        original_post: original_post.key(),
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddRepostContext<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + Repost::INIT_SPACE,
+        seeds = [
+            REPOST_SEED.as_bytes(),
+            reposter.key().as_ref(),
+            original_post.key().as_ref()
+        ],
+        bump
+    )]
+    pub repost: Account<'info, Repost>,
+
+    #[account(mut)]
+    pub original_post: Account<'info, Post>,
+
+    pub reposter: Signer<'info>,
+
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct RepostCreated {
+    pub header: EventHeader,
+    pub reposter: Pubkey,
+    pub original_post: Pubkey,
+}
@@ -0,0 +1,100 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "append chunk" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may append chunks, and only until the post is finalized
+/// - Enforce that the chunk does not exceed `CHUNK_LENGTH` bytes and that the post
+///   holds at most `MAX_POST_CHUNKS` chunks
+/// - Initialize a new `PostChunk` account as a PDA using seeds:
+///   [CHUNK_SEED, post pubkey, chunk index]
+/// - Increment the post's `chunk_count` and add the chunk to its `total_length`
+/// - Emit a `ChunkAppended` event for off-chain indexing
+/// - Charge the rent to the post's `payer`, which gets it back when the post is
+///   removed
+///
+/// Chunks are numbered from zero and read in order after the post's inline content.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CHUNK_LENGTH, CHUNK_SEED, CHUNK_VERSION, CONFIG_SEED, DISCRIMINATOR, MAX_POST_CHUNKS,
+};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post, PostChunk};
+
+pub fn append_chunk(ctx: Context<AppendChunkContext>, chunk_content: String) -> Result<()> {
+    require!(
+        chunk_content.len() <= CHUNK_LENGTH,
+        DepressError::ChunkTooLong
+    );
+
+    let post = &mut ctx.accounts.post;
+    require!(!post.finalized, DepressError::PostFinalized);
+    require!(
+        post.chunk_count < MAX_POST_CHUNKS,
+        DepressError::TooManyChunks
+    );
+
+    // Initialize the chunk account
+    let chunk = &mut ctx.accounts.chunk;
+    chunk.version = CHUNK_VERSION;
+    chunk.post = post.key();
+    chunk.index = post.chunk_count;
+    chunk.content = chunk_content;
+    chunk.bump = ctx.bumps.chunk;
+
+    // Update the post's chunk bookkeeping
+    post.chunk_count += 1;
+    post.total_length += chunk.content.len() as u32;
+
+    // Emit event
+    emit_cpi!(ChunkAppended {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post: post.key(),
+        index: chunk.index,
+        length: chunk.content.len() as u32,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AppendChunkContext<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + PostChunk::INIT_SPACE,
+        seeds = [
+            CHUNK_SEED.as_bytes(),
+            post.key().as_ref(),
+            post.chunk_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub chunk: Account<'info, PostChunk>,
+
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Must be the post's payer, which gets the rent back when the post is removed
+    #[account(mut, address = post.payer @ DepressError::InvalidPayer)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct ChunkAppended {
+    pub header: EventHeader,
+    pub post: Pubkey,
+    pub index: u32,
+    pub length: u32,
+}
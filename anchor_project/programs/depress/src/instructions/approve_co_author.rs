@@ -0,0 +1,77 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "approve co-authored post" functionality for the DePress program
+///
+/// Requirements:
+/// - Only a co-author listed on the post may approve it, once
+/// - Mark the co-author as approved
+/// - Once every co-author approved, the post goes live (subject to its
+///   publishing time) and accepts comments and reactions
+/// - Emit a `CoAuthorApproved` event with the number of pending approvals,
+///   followed by a `PostPublished` event when the approval publishes the post
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::errors::DepressError;
+use crate::instructions::PostPublished;
+use crate::states::{Config, EventHeader, Post};
+
+pub fn approve_co_author(ctx: Context<ApproveCoAuthorContext>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let co_author_key = ctx.accounts.co_author.key();
+
+    let co_author = post
+        .co_authors
+        .iter_mut()
+        .find(|co_author| co_author.author == co_author_key)
+        .ok_or(DepressError::NotCoAuthor)?;
+    require!(!co_author.approved, DepressError::CoAuthorAlreadyApproved);
+    co_author.approved = true;
+
+    let now = Clock::get()?.unix_timestamp;
+    let was_published = post.published;
+    post.published = post.is_published_at(now);
+
+    // Emit event
+    emit_cpi!(CoAuthorApproved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post: post.key(),
+        co_author: co_author_key,
+        pending: post.co_authors.iter().filter(|co| !co.approved).count() as u8,
+    });
+    if post.published && !was_published {
+        emit_cpi!(PostPublished {
+            header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+            post_author: post.post_author,
+            post: post.key(),
+            topic: post.topic.clone(),
+            published_at: now,
+        });
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveCoAuthorContext<'info> {
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+
+    pub co_author: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct CoAuthorApproved {
+    pub header: EventHeader,
+    pub post: Pubkey,
+    pub co_author: Pubkey,
+    // Co-authors still to approve
+    pub pending: u8,
+}
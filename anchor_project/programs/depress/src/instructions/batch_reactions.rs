@@ -0,0 +1,430 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "batch reactions" functionality for the DePress program
+///
+/// Requirements:
+/// - Apply up to `MAX_BATCH_REACTIONS` reaction additions or removals on posts and
+///   comments in one instruction, in order
+/// - Take the accounts of every entry, in order, via `remaining_accounts`:
+///   - add to a post: [post, post reaction PDA]
+///   - add to a comment: [parent post, comment, comment reaction PDA]
+///   - remove from a post: [post, post reaction PDA, reaction payer]
+///   - remove from a comment: [comment, comment reaction PDA, reaction payer]
+/// - Create or close the `ReactionPost`/`ReactionComment` PDAs and update the
+///   counters exactly like the single reaction instructions do
+/// - Accept an ephemeral key signing through a `Session` with `SESSION_REACT`
+///   in place of the wallet, for the whole batch
+/// - Emit the same event as the single instruction for every applied entry
+///
+/// Partial failures:
+/// - An entry whose target is already in the requested state (already reacted,
+///   or no reaction to remove) or whose post is unpublished or expired is
+///   skipped and reported with a `BatchReactionSkipped` event, as are additions
+///   on posts with a hook program, which must go through the single instructions
+/// - Missing or mismatched accounts, a wrong reaction payer and inconsistent
+///   counters abort the whole batch, leaving every target untouched
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    COMMENT_REACTION_SEED, CONFIG_SEED, DISCRIMINATOR, MAX_BATCH_REACTIONS, POST_REACTION_SEED,
+    REACTION_VERSION, SESSION_REACT, SESSION_SEED,
+};
+use crate::errors::DepressError;
+use crate::instructions::{
+    ReactionCommentAdded, ReactionCommentRemoved, ReactionPostAdded, ReactionPostRemoved,
+};
+use crate::states::{
+    BatchReaction, Comment, Config, EventHeader, Post, ReactionComment, ReactionPost,
+    ReactionTarget, ReactionType, Session,
+};
+use crate::utils::{close_account, create_pda_account, EventEmitter};
+
+pub fn batch_react<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchReactContext<'info>>,
+    reactions: Vec<BatchReaction>,
+) -> Result<()> {
+    require!(
+        !reactions.is_empty() && reactions.len() <= MAX_BATCH_REACTIONS,
+        DepressError::InvalidBatchSize
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let reaction_author = Session::authorize(
+        ctx.accounts.reaction_author.key(),
+        ctx.accounts.session.as_deref(),
+        SESSION_REACT,
+        now,
+    )?;
+
+    let batch = Batch {
+        author: reaction_author,
+        now,
+        payer: ctx.accounts.payer.to_account_info(),
+        rent_payer: Session::rent_payer(ctx.accounts.payer.key(), ctx.accounts.session.as_deref()),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    let mut accounts = ctx.remaining_accounts.iter();
+    let mut events = EventEmitter::new(
+        ctx.accounts.config.as_deref_mut(),
+        ctx.accounts.event_authority.to_account_info(),
+        ctx.bumps.event_authority,
+    );
+
+    for (index, entry) in reactions.into_iter().enumerate() {
+        let (target, skipped) = match (entry.target, entry.reaction) {
+            (ReactionTarget::Post, Some(reaction)) => {
+                batch.add_post_reaction(&mut accounts, &mut events, reaction)?
+            }
+            (ReactionTarget::Comment, Some(reaction)) => {
+                batch.add_comment_reaction(&mut accounts, &mut events, reaction)?
+            }
+            (ReactionTarget::Post, None) => {
+                batch.remove_post_reaction(&mut accounts, &mut events)?
+            }
+            (ReactionTarget::Comment, None) => {
+                batch.remove_comment_reaction(&mut accounts, &mut events)?
+            }
+        };
+
+        if let Some(reason) = skipped {
+            events.emit(|header| BatchReactionSkipped {
+                header,
+                author: reaction_author,
+                target,
+                index: index as u8,
+                reason,
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+// Shared state of a batch; each entry returns its target and, when it was
+// skipped, the reason why
+struct Batch<'info> {
+    author: Pubkey,
+    now: i64,
+    payer: AccountInfo<'info>,
+    // Recorded on the new reactions, see `Session::rent_payer`
+    rent_payer: Pubkey,
+    system_program: AccountInfo<'info>,
+}
+
+type EntryAccounts<'a, 'info> = std::slice::Iter<'a, AccountInfo<'info>>;
+
+fn next_account<'info>(
+    accounts: &mut EntryAccounts<'info, 'info>,
+) -> Result<&'info AccountInfo<'info>> {
+    accounts
+        .next()
+        .ok_or_else(|| DepressError::MissingBatchAccount.into())
+}
+
+impl<'info> Batch<'info> {
+    fn add_post_reaction(
+        &self,
+        accounts: &mut EntryAccounts<'info, 'info>,
+        events: &mut EventEmitter,
+        reaction: ReactionType,
+    ) -> Result<(Pubkey, Option<BatchSkipReason>)> {
+        let mut post = Account::<Post>::try_from(next_account(accounts)?)?;
+        let reaction_info = next_account(accounts)?;
+        let (reaction_key, bump) = Pubkey::find_program_address(
+            &[
+                POST_REACTION_SEED.as_bytes(),
+                self.author.as_ref(),
+                post.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            reaction_info.key(),
+            reaction_key,
+            DepressError::InvalidReactionAccount
+        );
+
+        if !post.is_published_at(self.now) || post.is_expired_at(self.now) {
+            return Ok((post.key(), Some(BatchSkipReason::Unavailable)));
+        }
+        if post.hook_program.is_some() {
+            return Ok((post.key(), Some(BatchSkipReason::Hooked)));
+        }
+        if reaction_info.owner == &crate::ID {
+            return Ok((post.key(), Some(BatchSkipReason::AlreadyReacted)));
+        }
+
+        create_pda_account(
+            reaction_info,
+            &self.payer,
+            &self.system_program,
+            DISCRIMINATOR + ReactionPost::INIT_SPACE,
+            &[
+                POST_REACTION_SEED.as_bytes(),
+                self.author.as_ref(),
+                post.key().as_ref(),
+                &[bump],
+            ],
+        )?;
+        match reaction {
+            ReactionType::Like => post.likes += 1,
+            ReactionType::Dislike => post.dislikes += 1,
+        }
+        ReactionPost {
+            version: REACTION_VERSION,
+            reaction_author: self.author,
+            payer: self.rent_payer,
+            parent_post: post.key(),
+            reaction: reaction.clone(),
+            bump,
+        }
+        .try_serialize(&mut &mut reaction_info.try_borrow_mut_data()?[..])?;
+        post.exit(&crate::ID)?;
+
+        events.emit(|header| ReactionPostAdded {
+            header,
+            author: self.author,
+            parent_post: post.key(),
+            reaction,
+        })?;
+
+        Ok((post.key(), None))
+    }
+
+    fn add_comment_reaction(
+        &self,
+        accounts: &mut EntryAccounts<'info, 'info>,
+        events: &mut EventEmitter,
+        reaction: ReactionType,
+    ) -> Result<(Pubkey, Option<BatchSkipReason>)> {
+        let post = Account::<Post>::try_from(next_account(accounts)?)?;
+        let mut comment = Account::<Comment>::try_from(next_account(accounts)?)?;
+        require_keys_eq!(
+            comment.parent_post,
+            post.key(),
+            DepressError::InvalidParentPost
+        );
+        let reaction_info = next_account(accounts)?;
+        let (reaction_key, bump) = Pubkey::find_program_address(
+            &[
+                COMMENT_REACTION_SEED.as_bytes(),
+                self.author.as_ref(),
+                comment.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            reaction_info.key(),
+            reaction_key,
+            DepressError::InvalidReactionAccount
+        );
+
+        if post.is_expired_at(self.now) {
+            return Ok((comment.key(), Some(BatchSkipReason::Unavailable)));
+        }
+        if post.hook_program.is_some() {
+            return Ok((comment.key(), Some(BatchSkipReason::Hooked)));
+        }
+        if reaction_info.owner == &crate::ID {
+            return Ok((comment.key(), Some(BatchSkipReason::AlreadyReacted)));
+        }
+
+        create_pda_account(
+            reaction_info,
+            &self.payer,
+            &self.system_program,
+            DISCRIMINATOR + ReactionComment::INIT_SPACE,
+            &[
+                COMMENT_REACTION_SEED.as_bytes(),
+                self.author.as_ref(),
+                comment.key().as_ref(),
+                &[bump],
+            ],
+        )?;
+        match reaction {
+            ReactionType::Like => comment.likes += 1,
+            ReactionType::Dislike => comment.dislikes += 1,
+        }
+        ReactionComment {
+            version: REACTION_VERSION,
+            reaction_author: self.author,
+            payer: self.rent_payer,
+            parent_comment: comment.key(),
+            reaction: reaction.clone(),
+            bump,
+        }
+        .try_serialize(&mut &mut reaction_info.try_borrow_mut_data()?[..])?;
+        comment.exit(&crate::ID)?;
+
+        events.emit(|header| ReactionCommentAdded {
+            header,
+            author: self.author,
+            parent_comment: comment.key(),
+            parent_post: comment.parent_post,
+            reaction,
+        })?;
+
+        Ok((comment.key(), None))
+    }
+
+    fn remove_post_reaction(
+        &self,
+        accounts: &mut EntryAccounts<'info, 'info>,
+        events: &mut EventEmitter,
+    ) -> Result<(Pubkey, Option<BatchSkipReason>)> {
+        let mut post = Account::<Post>::try_from(next_account(accounts)?)?;
+        let reaction_info = next_account(accounts)?;
+        let payer_info = next_account(accounts)?;
+        let (reaction_key, _) = Pubkey::find_program_address(
+            &[
+                POST_REACTION_SEED.as_bytes(),
+                self.author.as_ref(),
+                post.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            reaction_info.key(),
+            reaction_key,
+            DepressError::InvalidReactionAccount
+        );
+
+        if reaction_info.owner != &crate::ID {
+            return Ok((post.key(), Some(BatchSkipReason::NotReacted)));
+        }
+        let post_reaction = Account::<ReactionPost>::try_from(reaction_info)?;
+        require_keys_eq!(
+            post_reaction.payer,
+            payer_info.key(),
+            DepressError::InvalidPayer
+        );
+
+        match post_reaction.reaction {
+            ReactionType::Like => {
+                require!(post.likes > 0, DepressError::InvalidReactionState);
+                post.likes -= 1;
+            }
+            ReactionType::Dislike => {
+                require!(post.dislikes > 0, DepressError::InvalidReactionState);
+                post.dislikes -= 1;
+            }
+        }
+        post.exit(&crate::ID)?;
+        close_account(reaction_info, payer_info)?;
+
+        events.emit(|header| ReactionPostRemoved {
+            header,
+            author: self.author,
+            parent_post: post.key(),
+            reaction: post_reaction.reaction.clone(),
+        })?;
+
+        Ok((post.key(), None))
+    }
+
+    fn remove_comment_reaction(
+        &self,
+        accounts: &mut EntryAccounts<'info, 'info>,
+        events: &mut EventEmitter,
+    ) -> Result<(Pubkey, Option<BatchSkipReason>)> {
+        let mut comment = Account::<Comment>::try_from(next_account(accounts)?)?;
+        let reaction_info = next_account(accounts)?;
+        let payer_info = next_account(accounts)?;
+        let (reaction_key, _) = Pubkey::find_program_address(
+            &[
+                COMMENT_REACTION_SEED.as_bytes(),
+                self.author.as_ref(),
+                comment.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            reaction_info.key(),
+            reaction_key,
+            DepressError::InvalidReactionAccount
+        );
+
+        if reaction_info.owner != &crate::ID {
+            return Ok((comment.key(), Some(BatchSkipReason::NotReacted)));
+        }
+        let comment_reaction = Account::<ReactionComment>::try_from(reaction_info)?;
+        require_keys_eq!(
+            comment_reaction.payer,
+            payer_info.key(),
+            DepressError::InvalidPayer
+        );
+
+        match comment_reaction.reaction {
+            ReactionType::Like => {
+                require!(comment.likes > 0, DepressError::InvalidReactionState);
+                comment.likes -= 1;
+            }
+            ReactionType::Dislike => {
+                require!(comment.dislikes > 0, DepressError::InvalidReactionState);
+                comment.dislikes -= 1;
+            }
+        }
+        comment.exit(&crate::ID)?;
+        close_account(reaction_info, payer_info)?;
+
+        events.emit(|header| ReactionCommentRemoved {
+            header,
+            author: self.author,
+            parent_comment: comment.key(),
+            parent_post: comment.parent_post,
+            reaction: comment_reaction.reaction.clone(),
+        })?;
+
+        Ok((comment.key(), None))
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BatchReactContext<'info> {
+    // The wallet, or a session key acting for it
+    pub reaction_author: Signer<'info>,
+
+    // Funds the new reaction accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            session.authority.as_ref(),
+            reaction_author.key().as_ref(),
+        ],
+        bump = session.bump,
+    )]
+    pub session: Option<Account<'info, Session>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum BatchSkipReason {
+    // The author already reacted to the target
+    AlreadyReacted,
+    // There is no reaction of the author to remove
+    NotReacted,
+    // The post is not published yet or has expired
+    Unavailable,
+    // The post has a hook program, which only the single instructions invoke
+    Hooked,
+}
+
+#[event]
+pub struct BatchReactionSkipped {
+    pub header: EventHeader,
+    pub author: Pubkey,
+    // The post or comment of the skipped entry
+    pub target: Pubkey,
+    // Position of the entry in the batch
+    pub index: u8,
+    pub reason: BatchSkipReason,
+}
@@ -0,0 +1,130 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "close expired post" functionality for the DePress program
+///
+/// Requirements:
+/// - Anyone may call this instruction, no author signature is required
+/// - Reject posts without `expires_at` or whose expiry time has not passed yet
+/// - Close the `Post` account and return its lamports to its recorded `payer`
+/// - Release the post's dependent accounts exactly like `remove_post`: its `Poll`,
+///   its `Profile` pin, its `PostChunk` and `PostRevision` accounts passed via
+///   `remaining_accounts`, its `Tag` usages and its `PostIndex`, refunding the rent
+///   of revisions to their payers and all other rent to the post's `payer`
+/// - Emit a `PostExpired` event for off-chain indexing
+///
+/// The caller only pays the transaction fee; none of the reclaimed rent goes to them.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, INDEX_SEED, POLL_SEED, POST_SEED, PROFILE_SEED};
+use crate::errors::DepressError;
+use crate::instructions::release_post_accounts;
+use crate::states::{Config, EventHeader, Poll, Post, PostIndex, Profile};
+use crate::utils::EventEmitter;
+
+pub fn close_expired_post<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseExpiredPostContext<'info>>,
+) -> Result<()> {
+    // The account will be automatically closed due to the `close` constraint
+
+    let post = &ctx.accounts.post;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(post.is_expired_at(now), DepressError::PostNotExpired);
+
+    release_post_accounts(
+        post,
+        ctx.accounts.poll.as_ref(),
+        ctx.accounts.profile.as_mut(),
+        ctx.remaining_accounts,
+        &ctx.accounts.payer.to_account_info(),
+        &mut EventEmitter::new(
+            ctx.accounts.config.as_deref_mut(),
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.bumps.event_authority,
+        ),
+    )?;
+
+    // Emit event
+    emit_cpi!(PostExpired {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post_author: post.post_author,
+        post: post.key(),
+        topic: post.topic.clone(),
+        expired_at: now,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseExpiredPostContext<'info> {
+    #[account(
+        mut,
+        has_one = post_author,
+        has_one = payer,
+        close = payer,
+
+        seeds = [
+            POST_SEED.as_bytes(),
+            post.topic.as_bytes(),
+            post_author.key().as_ref(),
+        ],
+        bump = post.bump,
+    )]
+    pub post: Account<'info, Post>,
+
+    /// CHECK: Bound to the post through `has_one`.
+    pub post_author: UncheckedAccount<'info>,
+
+    /// CHECK: Bound to the post through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            POLL_SEED.as_bytes(),
+            post.key().as_ref(),
+        ],
+        bump = poll.bump,
+    )]
+    pub poll: Option<Account<'info, Poll>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            post_author.key().as_ref(),
+        ],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+
+    // Closed together with the post
+    #[account(
+        mut,
+        close = payer,
+        seeds = [INDEX_SEED.as_bytes(), post.global_id.to_le_bytes().as_ref()],
+        bump = post_index.bump,
+    )]
+    pub post_index: Account<'info, PostIndex>,
+}
+
+#[event]
+pub struct PostExpired {
+    pub header: EventHeader,
+    pub post_author: Pubkey,
+    pub post: Pubkey,
+    pub topic: String,
+    pub expired_at: i64,
+}
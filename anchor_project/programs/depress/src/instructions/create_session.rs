@@ -0,0 +1,121 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "create session" functionality for the DePress program
+///
+/// Requirements:
+/// - The wallet (`authority`) signs the session
+/// - Initialize a new `Session` account as a PDA using seeds:
+///   [SESSION_SEED, authority pubkey, session key pubkey]
+/// - `expires_at` must lie in the future and at most `MAX_SESSION_DURATION` ahead
+/// - `permissions` must be a non-empty subset of `SESSION_ACTIONS`
+/// - Optionally transfer `top_up` lamports from `payer` to the session key so it
+///   can pay for the accounts it creates
+/// - Emit a `SessionCreated` event
+/// - Charge the rent to `payer`, which may differ from the wallet, and record it
+///   on the account so the rent goes back to it on close
+///
+/// The session key then signs `post_add`, `comment_add` and the reaction
+/// instructions in place of the wallet, passing the session as `session`.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::{
+    CONFIG_SEED, DISCRIMINATOR, MAX_SESSION_DURATION, SESSION_ACTIONS, SESSION_SEED,
+    SESSION_VERSION,
+};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Session};
+
+pub fn create_session(
+    ctx: Context<CreateSessionContext>,
+    expires_at: i64,
+    permissions: u8,
+    top_up: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        expires_at > now && expires_at - now <= MAX_SESSION_DURATION,
+        DepressError::InvalidSessionExpiry
+    );
+    require!(
+        permissions != 0 && permissions & !SESSION_ACTIONS == 0,
+        DepressError::InvalidSessionPermissions
+    );
+
+    let session = &mut ctx.accounts.session;
+    session.version = SESSION_VERSION;
+    session.authority = ctx.accounts.authority.key();
+    session.session_key = ctx.accounts.session_key.key();
+    session.payer = ctx.accounts.payer.key();
+    session.expires_at = expires_at;
+    session.permissions = permissions;
+    session.bump = ctx.bumps.session;
+
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.session_key.to_account_info(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    // Emit event
+    emit_cpi!(SessionCreated {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        authority: session.authority,
+        session_key: session.session_key,
+        expires_at,
+        permissions,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateSessionContext<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + Session::INIT_SPACE,
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            authority.key().as_ref(),
+            session_key.key().as_ref(),
+        ],
+        bump
+    )]
+    pub session: Account<'info, Session>,
+
+    pub authority: Signer<'info>,
+
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Ephemeral keypair being authorized; only receives the top-up.
+    #[account(mut)]
+    pub session_key: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub header: EventHeader,
+    pub authority: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub permissions: u8,
+}
@@ -0,0 +1,60 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "edit comment labels" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the comment author may change the comment's labels
+/// - Validate and replace the content labels (language, content warning, flags)
+/// - Emit a `CommentLabelsEdited` event for off-chain indexing
+///
+/// The comment content itself cannot change since its hash is part of the PDA seeds.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::errors::DepressError;
+use crate::states::{Comment, Config, ContentLabels, EventHeader};
+
+pub fn edit_comment_labels(
+    ctx: Context<EditCommentLabelsContext>,
+    labels: ContentLabels,
+) -> Result<()> {
+    labels.validate()?;
+
+    let comment = &mut ctx.accounts.comment;
+    comment.labels = labels.clone();
+
+    // Emit event
+    emit_cpi!(CommentLabelsEdited {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        author: comment.comment_author,
+        comment: comment.key(),
+        parent_post: comment.parent_post,
+        labels,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EditCommentLabelsContext<'info> {
+    #[account(mut, has_one = comment_author @ DepressError::InvalidOwner)]
+    pub comment: Account<'info, Comment>,
+
+    pub comment_author: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct CommentLabelsEdited {
+    pub header: EventHeader,
+    pub author: Pubkey,
+    pub comment: Pubkey,
+    pub parent_post: Pubkey,
+    pub labels: ContentLabels,
+}
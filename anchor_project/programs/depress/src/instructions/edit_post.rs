@@ -0,0 +1,169 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "edit post" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may edit the post, and only until it is finalized
+/// - Reject drafts, which are revised in place through `draft_save` instead
+/// - Enforce that the new `content` does not exceed the maximum allowed length
+/// - When a `content_ref` is given, validate it and replace the post's reference
+///   with it; the inline content must stay empty while the post has a reference
+/// - Initialize a new `PostRevision` account as a PDA using seeds:
+///   [REVISION_SEED, post pubkey, revision number]
+///   holding the previous content, the previous content reference and the edit
+///   timestamp
+/// - Increment the post's `revision_count`
+/// - Re-extract hashtags, drop a usage from the `Tag` index account of every hashtag
+///   the edit removes and record the post in that of every newly added one; the
+///   removed tags are passed in order via `remaining_accounts`, followed by the
+///   added ones
+/// - Replace the content labels when new ones are given
+/// - Emit a `PostEdited` event for off-chain indexing
+/// - Charge the rent to `payer`, which may differ from the author, and record it
+///   on the account so the rent goes back to it on close
+///
+/// Revisions are numbered from zero; revision `n` holds the content as it was
+/// before the `n + 1`-th edit. Authors reclaim their rent with `revision_prune`.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CONFIG_SEED, CONTENT_LENGTH, DISCRIMINATOR, REVISION_SEED, REVISION_VERSION,
+};
+use crate::errors::DepressError;
+use crate::states::{Config, ContentLabels, ContentRef, EventHeader, Post, PostRevision, Tag};
+use crate::utils::{index_hashtags, unindex_hashtags};
+
+pub fn edit_post<'info>(
+    ctx: Context<'_, '_, 'info, 'info, EditPostContext<'info>>,
+    post_content: String,
+    labels: Option<ContentLabels>,
+    content_ref: Option<ContentRef>,
+) -> Result<()> {
+    // Validate content length
+    require!(
+        post_content.len() <= CONTENT_LENGTH,
+        DepressError::ContentTooLong
+    );
+
+    if let Some(labels) = &labels {
+        labels.validate()?;
+    }
+    if let Some(content_ref) = &content_ref {
+        content_ref.validate()?;
+    }
+
+    let hashtags = Tag::parse_hashtags(&post_content)?;
+
+    let post = &mut ctx.accounts.post;
+    require!(!post.finalized, DepressError::PostFinalized);
+    require!(!post.is_draft, DepressError::PostIsDraft);
+    require!(
+        (post.content_ref.is_none() && content_ref.is_none()) || post_content.is_empty(),
+        DepressError::InlineContentNotAllowed
+    );
+    let edited_at = Clock::get()?.unix_timestamp;
+
+    post.total_length = post.total_length - post.content.len() as u32 + post_content.len() as u32;
+
+    // Store the previous content as a revision
+    let revision = &mut ctx.accounts.revision;
+    revision.version = REVISION_VERSION;
+    revision.post = post.key();
+    revision.post_author = post.post_author;
+    revision.payer = ctx.accounts.payer.key();
+    revision.revision_number = post.revision_count;
+    revision.previous_content = std::mem::replace(&mut post.content, post_content);
+    revision.previous_content_ref = match content_ref {
+        Some(content_ref) => post.content_ref.replace(content_ref),
+        None => post.content_ref.clone(),
+    };
+    revision.edited_at = edited_at;
+    revision.bump = ctx.bumps.revision;
+
+    post.revision_count += 1;
+    if let Some(labels) = labels {
+        post.labels = labels;
+    }
+
+    // Only hashtags the post did not carry before count as new usages, and only
+    // those it no longer carries lose theirs
+    let removed_hashtags: Vec<String> = post
+        .hashtags
+        .iter()
+        .filter(|tag| !hashtags.contains(tag))
+        .cloned()
+        .collect();
+    let new_hashtags: Vec<String> = hashtags
+        .iter()
+        .filter(|tag| !post.hashtags.contains(tag))
+        .cloned()
+        .collect();
+    post.hashtags = hashtags;
+
+    let (removed_tags, new_tags) = ctx
+        .remaining_accounts
+        .split_at(removed_hashtags.len().min(ctx.remaining_accounts.len()));
+    unindex_hashtags(&removed_hashtags, removed_tags)?;
+    index_hashtags(
+        &new_hashtags,
+        new_tags,
+        post.key(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Emit event
+    emit_cpi!(PostEdited {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post_author: post.post_author,
+        post: post.key(),
+        revision_number: revision.revision_number,
+        edited_at,
+        labels: post.labels.clone(),
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EditPostContext<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + PostRevision::INIT_SPACE,
+        seeds = [
+            REVISION_SEED.as_bytes(),
+            post.key().as_ref(),
+            post.revision_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub revision: Account<'info, PostRevision>,
+
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PostEdited {
+    pub header: EventHeader,
+    pub post_author: Pubkey,
+    pub post: Pubkey,
+    pub revision_number: u32,
+    pub edited_at: i64,
+    pub labels: ContentLabels,
+}
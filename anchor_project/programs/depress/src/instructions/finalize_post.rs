@@ -0,0 +1,54 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "finalize post" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may finalize the post
+/// - Freeze the post: no more chunks can be appended and the content can no
+///   longer be edited
+/// - Emit a `PostFinalized` event with the final chunk count and length
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post};
+
+pub fn finalize_post(ctx: Context<FinalizePostContext>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    require!(!post.finalized, DepressError::PostFinalized);
+
+    post.finalized = true;
+
+    // Emit event
+    emit_cpi!(PostFinalized {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post: post.key(),
+        chunk_count: post.chunk_count,
+        total_length: post.total_length,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizePostContext<'info> {
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PostFinalized {
+    pub header: EventHeader,
+    pub post: Pubkey,
+    pub chunk_count: u32,
+    pub total_length: u32,
+}
@@ -0,0 +1,45 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "initialize config" functionality for the DePress program
+///
+/// Requirements:
+/// - Create the program-wide `Config` account once, as a PDA using seeds: [CONFIG_SEED]
+/// - Anyone may create it and pay its rent, it holds no authority
+/// - Start the global event sequence at zero; every instruction emitting events
+///   takes the config and numbers its events from it
+/// - Start the global post sequence at zero; every instruction creating posts
+///   takes the next id from it
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, CONFIG_VERSION, DISCRIMINATOR};
+use crate::states::Config;
+
+pub fn initialize_config(ctx: Context<InitializeConfigContext>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.version = CONFIG_VERSION;
+    config.event_sequence = 0;
+    config.post_count = 0;
+    config.bump = ctx.bumps.config;
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeConfigContext<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + Config::INIT_SPACE,
+        seeds = [CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,174 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "migrate account" functionality for the DePress program
+///
+/// Requirements:
+/// - Anyone may rewrite a `Post`, `Comment`, `ReactionPost` or `ReactionComment`
+///   still in the payerless layout of 0.1 into the current layout of its type,
+///   recognized by its discriminator and size
+/// - Fill the fields missing from the old layout with defaults: the author as payer
+///   and published, unlabeled posts
+/// - Give posts from before the global post sequence the next `global_id` and create
+///   their `PostIndex`, passed as `post_index`
+/// - Grow the account to the current size, with the signer paying the extra rent
+/// - Reject accounts not owned by the program or already in the current layout
+/// - Emit an `AccountMigrated` event
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, Space};
+
+use crate::constants::{
+    CONFIG_SEED, DISCRIMINATOR, INDEX_SEED, INDEX_VERSION, LEGACY_COMMENT_DISCRIMINATOR,
+    LEGACY_POST_DISCRIMINATOR,
+};
+use crate::errors::DepressError;
+use crate::states::{
+    payerless, Comment, Config, EventHeader, Post, PostIndex, ReactionComment, ReactionPost,
+};
+use crate::utils::{create_pda_account, resize_account};
+
+pub fn migrate_account(ctx: Context<MigrateAccountContext>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let migrated = current_layout(&account.try_borrow_data()?)?;
+
+    let (data, space) = match migrated {
+        Migrated::Post(mut post) => {
+            let global_id = ctx.accounts.config.next_post_id();
+            let index_info = ctx
+                .accounts
+                .post_index
+                .as_ref()
+                .ok_or(DepressError::InvalidPostIndex)?
+                .to_account_info();
+            let id_bytes = global_id.to_le_bytes();
+            let (index_key, bump) =
+                Pubkey::find_program_address(&[INDEX_SEED.as_bytes(), &id_bytes], &crate::ID);
+            require_keys_eq!(index_info.key(), index_key, DepressError::InvalidPostIndex);
+
+            create_pda_account(
+                &index_info,
+                &payer,
+                &system_program,
+                DISCRIMINATOR + PostIndex::INIT_SPACE,
+                &[INDEX_SEED.as_bytes(), &id_bytes, &[bump]],
+            )?;
+            PostIndex {
+                version: INDEX_VERSION,
+                post: account.key(),
+                bump,
+            }
+            .try_serialize(&mut &mut index_info.try_borrow_mut_data()?[..])?;
+
+            post.global_id = global_id;
+            let mut data = Vec::new();
+            post.try_serialize(&mut data)?;
+            (data, DISCRIMINATOR + Post::INIT_SPACE)
+        }
+        Migrated::Account { data, space } => (data, space),
+    };
+    write_layout(&account, &payer, &system_program, &data, space)?;
+
+    emit_cpi!(AccountMigrated {
+        header: ctx.accounts.config.next_header()?,
+        account: account.key(),
+    });
+
+    Ok(())
+}
+
+enum Migrated {
+    // A post from before the global post sequence, still to take its `global_id`
+    Post(Box<Post>),
+    // Any other account, serialized in its current layout of `space` bytes
+    Account { data: Vec<u8>, space: usize },
+}
+
+fn migrated<T: AccountSerialize + Space>(account: T) -> Result<Migrated> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data)?;
+    Ok(Migrated::Account {
+        data,
+        space: DISCRIMINATOR + T::INIT_SPACE,
+    })
+}
+
+/// Finds the layout `data` was written in and converts it to the current one.
+fn current_layout(data: &[u8]) -> Result<Migrated> {
+    require!(data.len() >= DISCRIMINATOR, DepressError::NotLegacyAccount);
+    let (discriminator, mut body) = data.split_at(DISCRIMINATOR);
+    let len = body.len();
+
+    if discriminator == LEGACY_POST_DISCRIMINATOR && len == payerless::Post::INIT_SPACE {
+        return Ok(Migrated::Post(Box::new(
+            payerless::Post::deserialize(&mut body)?.into(),
+        )));
+    } else if discriminator == LEGACY_COMMENT_DISCRIMINATOR && len == payerless::Comment::INIT_SPACE
+    {
+        return migrated(Comment::from(payerless::Comment::deserialize(&mut body)?));
+    } else if discriminator == ReactionPost::DISCRIMINATOR
+        && len == payerless::ReactionPost::INIT_SPACE
+    {
+        return migrated(ReactionPost::from(payerless::ReactionPost::deserialize(
+            &mut body,
+        )?));
+    } else if discriminator == ReactionComment::DISCRIMINATOR
+        && len == payerless::ReactionComment::INIT_SPACE
+    {
+        return migrated(ReactionComment::from(
+            payerless::ReactionComment::deserialize(&mut body)?,
+        ));
+    }
+
+    err!(DepressError::NotLegacyAccount)
+}
+
+/// Overwrites `account` with `data`, first growing it to `space` bytes.
+fn write_layout<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    data: &[u8],
+    space: usize,
+) -> Result<()> {
+    if account.data_len() < space {
+        resize_account(account, payer, system_program, space)?;
+    }
+
+    let mut account_data = account.try_borrow_mut_data()?;
+    account_data.fill(0);
+    account_data[..data.len()].copy_from_slice(data);
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateAccountContext<'info> {
+    /// CHECK: Owned by the program; the handler recognizes its layout.
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    // Pays the rent of the grown account and of any created post index
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Created for posts from before the global post sequence, at the next post id
+    /// CHECK: Verified and created by the handler.
+    #[account(mut)]
+    pub post_index: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events and posts from before the global post sequence
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub header: EventHeader,
+    pub account: Pubkey,
+}
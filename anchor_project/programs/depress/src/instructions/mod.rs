@@ -1,3 +1,11 @@
+// Config instructions
+pub use initialize_config::*;
+pub mod initialize_config;
+
+// Migration instructions
+pub use migrate_account::*;
+pub mod migrate_account;
+
 // Post instructions
 pub use add_post::*;
 pub mod add_post;
@@ -5,6 +13,63 @@ pub mod add_post;
 pub use remove_post::*;
 pub mod remove_post;
 
+pub use edit_post::*;
+pub mod edit_post;
+
+pub use publish_due::*;
+pub mod publish_due;
+
+pub use close_expired_post::*;
+pub mod close_expired_post;
+
+// Draft instructions
+pub use save_draft::*;
+pub mod save_draft;
+
+pub use publish_draft::*;
+pub mod publish_draft;
+
+// Co-author instructions
+pub use approve_co_author::*;
+pub mod approve_co_author;
+
+pub use vote_removal::*;
+pub mod vote_removal;
+
+pub use remove_voted_post::*;
+pub mod remove_voted_post;
+
+pub use tip_post::*;
+pub mod tip_post;
+
+// Session instructions
+pub use create_session::*;
+pub mod create_session;
+
+pub use revoke_session::*;
+pub mod revoke_session;
+
+pub use prune_revision::*;
+pub mod prune_revision;
+
+// Long-form post instructions
+pub use append_chunk::*;
+pub mod append_chunk;
+
+pub use finalize_post::*;
+pub mod finalize_post;
+
+// Hook instructions
+pub use set_post_hook::*;
+pub mod set_post_hook;
+
+// Relayed instructions
+pub use add_relayed_post::*;
+pub mod add_relayed_post;
+
+pub use add_relayed_comment::*;
+pub mod add_relayed_comment;
+
 // Post reaction instructions
 pub use add_reaction_post::*;
 pub mod add_reaction_post;
@@ -19,9 +84,40 @@ pub mod add_comment;
 pub use remove_comment::*;
 pub mod remove_comment;
 
+pub use edit_comment_labels::*;
+pub mod edit_comment_labels;
+
+// Batch reaction instructions
+pub use batch_reactions::*;
+pub mod batch_reactions;
+
 // Comment Reaction instructions
 pub use add_reaction_comment::*;
 pub mod add_reaction_comment;
 
 pub use remove_reaction_comment::*;
 pub mod remove_reaction_comment;
+
+// Repost instructions
+pub use add_repost::*;
+pub mod add_repost;
+
+pub use remove_repost::*;
+pub mod remove_repost;
+
+// Poll instructions
+pub use add_poll::*;
+pub mod add_poll;
+
+pub use vote_poll::*;
+pub mod vote_poll;
+
+pub use remove_poll_vote::*;
+pub mod remove_poll_vote;
+
+// Pin instructions
+pub use pin_post::*;
+pub mod pin_post;
+
+pub use unpin_post::*;
+pub mod unpin_post;
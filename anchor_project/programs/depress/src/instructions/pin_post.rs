@@ -0,0 +1,86 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "pin post" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may pin their post
+/// - Create the author's `Profile` account on first use as a PDA using seeds:
+///   [PROFILE_SEED, author pubkey]
+/// - Append the post to the profile's pinned posts, at most `MAX_PINNED_POSTS`
+/// - Mark the post as pinned so `post_remove` drops the pin
+/// - Emit a `PostPinned` event for off-chain indexing
+/// - Charge the profile's rent to `payer`, which may differ from the author
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CONFIG_SEED, DISCRIMINATOR, MAX_PINNED_POSTS, PROFILE_SEED, PROFILE_VERSION,
+};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post, Profile};
+
+pub fn pin_post(ctx: Context<PinPostContext>) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let post = &mut ctx.accounts.post;
+
+    require!(!post.is_pinned, DepressError::PostAlreadyPinned);
+    require!(
+        profile.pinned_posts.len() < MAX_PINNED_POSTS,
+        DepressError::TooManyPinnedPosts
+    );
+
+    // Initialize the profile on first use
+    profile.version = PROFILE_VERSION;
+    profile.author = ctx.accounts.post_author.key();
+    profile.bump = ctx.bumps.profile;
+
+    profile.pinned_posts.push(post.key());
+    post.is_pinned = true;
+
+    // Emit event
+    emit_cpi!(PostPinned {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post_author: profile.author,
+        post: post.key(),
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PinPostContext<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DISCRIMINATOR + Profile::INIT_SPACE,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            post_author.key().as_ref()
+        ],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PostPinned {
+    pub header: EventHeader,
+    pub post_author: Pubkey,
+    pub post: Pubkey,
+}
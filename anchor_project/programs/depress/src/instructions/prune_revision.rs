@@ -0,0 +1,69 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "prune revision" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may prune revisions of their post
+/// - Close the `PostRevision` account and refund rent to its recorded `payer`
+/// - Verify the PDA is derived using the same seeds as during the edit:
+///   [REVISION_SEED, post pubkey, revision number]
+/// - Emit a `RevisionPruned` event for off-chain indexing
+///
+/// The post itself is not required, so revisions of removed posts can be pruned too.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, REVISION_SEED};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, PostRevision};
+
+pub fn prune_revision(ctx: Context<PruneRevisionContext>) -> Result<()> {
+    let revision = &ctx.accounts.revision;
+
+    // Emit event before account is closed
+    emit_cpi!(RevisionPruned {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post_author: revision.post_author,
+        post: revision.post,
+        revision_number: revision.revision_number,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PruneRevisionContext<'info> {
+    #[account(
+        mut,
+        has_one = post_author @ DepressError::InvalidOwner,
+        has_one = payer,
+        close = payer,
+        seeds = [
+            REVISION_SEED.as_bytes(),
+            revision.post.as_ref(),
+            revision.revision_number.to_le_bytes().as_ref(),
+        ],
+        bump = revision.bump,
+    )]
+    pub revision: Account<'info, PostRevision>,
+
+    pub post_author: Signer<'info>,
+
+    /// CHECK: Bound to the revision through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct RevisionPruned {
+    pub header: EventHeader,
+    pub post_author: Pubkey,
+    pub post: Pubkey,
+    pub revision_number: u32,
+}
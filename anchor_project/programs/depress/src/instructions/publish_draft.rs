@@ -0,0 +1,66 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "publish draft" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may publish the draft
+/// - Reject posts that are not drafts and drafts that have already expired
+/// - Record the post in the `Tag` index account of every hashtag, passed in order
+///   via `remaining_accounts` as [TAG_SEED, tag] PDAs (created on first use)
+/// - Emit the `PostCreated`, `Mentioned` and `QuotePostCreated` events that
+///   `post_add` holds back for drafts
+///
+/// A draft with a `publish_at` still in the future becomes a scheduled post and
+/// goes live through `publish_due` as usual.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::errors::DepressError;
+use crate::instructions::announce_post;
+use crate::states::{Config, Post};
+use crate::utils::EventEmitter;
+
+pub fn publish_draft<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PublishDraftContext<'info>>,
+) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(post.is_draft, DepressError::PostNotDraft);
+    require!(!post.is_expired_at(now), DepressError::PostExpired);
+
+    post.is_draft = false;
+    post.published = post.is_published_at(now);
+
+    announce_post(
+        post,
+        ctx.remaining_accounts,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &mut EventEmitter::new(
+            ctx.accounts.config.as_deref_mut(),
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.bumps.event_authority,
+        ),
+    )
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PublishDraftContext<'info> {
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Funds the new tag accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
@@ -0,0 +1,60 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "publish due post" functionality for the DePress program
+///
+/// Requirements:
+/// - Anyone may call this instruction, no author signature is required
+/// - Reject posts whose publishing time has not passed yet (checked via `Clock`)
+/// - Reject posts that are already published
+/// - Mark the post as published and emit a `PostPublished` event
+///
+/// Comments and reactions are accepted as soon as the publishing time passes;
+/// this instruction exists so indexers get a single event when the post goes live.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post};
+
+pub fn publish_due(ctx: Context<PublishDueContext>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!post.published, DepressError::PostAlreadyPublished);
+    require!(post.is_published_at(now), DepressError::PostNotPublished);
+
+    post.published = true;
+
+    // Emit event
+    emit_cpi!(PostPublished {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post_author: post.post_author,
+        post: post.key(),
+        topic: post.topic.clone(),
+        published_at: now,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PublishDueContext<'info> {
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PostPublished {
+    pub header: EventHeader,
+    pub post_author: Pubkey,
+    pub post: Pubkey,
+    pub topic: String,
+    pub published_at: i64,
+}
@@ -4,7 +4,7 @@
 ///
 /// Requirements:
 /// - Only the comment author may delete the comment
-/// - Close the comment account and return rent to the author
+/// - Close the comment account and return rent to its recorded `payer`
 /// - Verify PDA is derived using the same seeds as during creation:
 ///   [COMMENT_SEED, parent_post, author, SHA256(content)]
 /// - Emit a `CommentRemoved` event for off-chain indexing
@@ -15,8 +15,8 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash::hash;
 
-use crate::constants::COMMENT_SEED;
-use crate::states::Comment;
+use crate::constants::{COMMENT_SEED, CONFIG_SEED};
+use crate::states::{Comment, Config, EventHeader};
 
 pub fn remove_comment(ctx: Context<RemoveCommentContext>) -> Result<()> {
     let comment = &ctx.accounts.comment;
@@ -25,8 +25,10 @@ pub fn remove_comment(ctx: Context<RemoveCommentContext>) -> Result<()> {
     // (Not strictly needed due to PDA constraint, but improves event accuracy)
     // require!(comment.content == content, DepressError::InvalidContent);
 
-    emit!(CommentRemoved {
+    emit_cpi!(CommentRemoved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
         author: comment.comment_author,
+        comment: comment.key(),
         parent_post: comment.parent_post.key(),
         content: comment.content.clone(),
     });
@@ -34,15 +36,20 @@ pub fn remove_comment(ctx: Context<RemoveCommentContext>) -> Result<()> {
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RemoveCommentContext<'info> {
-    #[account(mut)]
     pub comment_author: Signer<'info>,
 
+    /// CHECK: Bound to the comment through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
     #[account(
         mut,
         has_one = comment_author,
-        close = comment_author,
+        has_one = payer,
+        close = payer,
         seeds = [
             COMMENT_SEED.as_bytes(),
             comment_author.key().as_ref(),
@@ -52,11 +59,17 @@ pub struct RemoveCommentContext<'info> {
         bump = comment.bump,
     )]
     pub comment: Account<'info, Comment>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
 }
 
 #[event]
 pub struct CommentRemoved {
+    pub header: EventHeader,
     pub author: Pubkey,
+    pub comment: Pubkey,
     pub parent_post: Pubkey,
     pub content: String,
 }
@@ -0,0 +1,102 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "remove orphaned poll vote" functionality for the DePress
+/// program
+///
+/// Requirements:
+/// - Anyone may close a `PollVote` whose poll was closed with its post, possibly
+///   re-created since for a new post at the same address
+/// - A poll still at the voted address must be passed with its post as `post`,
+///   whose `global_id` must differ from the one recorded on the vote
+/// - Close the `PollVote` account and refund rent to its recorded `payer`
+/// - Emit a `PollVoteRemoved` event for off-chain indexing
+///
+/// Votes on live polls are never removed, so their tallies stay final.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, POLL_VOTE_SEED};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Poll, PollVote, Post};
+
+pub fn remove_orphaned_poll_vote(ctx: Context<RemoveOrphanedPollVoteContext>) -> Result<()> {
+    let poll_vote = &ctx.accounts.poll_vote;
+    let poll_info = &ctx.accounts.poll;
+
+    // A poll at the voted address must belong to a post re-created since
+    if !poll_info.data_is_empty() {
+        require_keys_eq!(
+            *poll_info.owner,
+            crate::ID,
+            DepressError::InvalidPollAccount
+        );
+        let poll = Poll::try_deserialize(&mut &poll_info.try_borrow_data()?[..])?;
+        let post_info = ctx
+            .accounts
+            .post
+            .as_ref()
+            .ok_or(DepressError::InvalidPostAccount)?;
+        require_keys_eq!(
+            post_info.key(),
+            poll.parent_post,
+            DepressError::InvalidPostAccount
+        );
+        let post = Post::try_deserialize(&mut &post_info.try_borrow_data()?[..])?;
+        require!(!poll_vote.votes_on(&post), DepressError::PollExists);
+    }
+
+    emit_cpi!(PollVoteRemoved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        voter: poll_vote.voter,
+        poll: poll_vote.poll,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveOrphanedPollVoteContext<'info> {
+    #[account(
+        mut,
+        has_one = voter @ DepressError::InvalidOwner,
+        has_one = poll,
+        has_one = payer,
+        close = payer,
+        seeds = [
+            POLL_VOTE_SEED.as_bytes(),
+            voter.key().as_ref(),
+            poll.key().as_ref(),
+        ],
+        bump = poll_vote.bump,
+    )]
+    pub poll_vote: Account<'info, PollVote>,
+
+    /// CHECK: Must be closed or belong to another post, which is verified by the handler.
+    pub poll: UncheckedAccount<'info>,
+
+    // The post of the poll at the voted address, when there still is one
+    /// CHECK: Verified against the poll by the handler.
+    pub post: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Bound to the poll vote through `has_one`.
+    pub voter: UncheckedAccount<'info>,
+
+    /// CHECK: Bound to the poll vote through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PollVoteRemoved {
+    pub header: EventHeader,
+    pub voter: Pubkey,
+    pub poll: Pubkey,
+}
@@ -4,9 +4,25 @@
 ///
 /// Requirements:
 /// - Only the original post author may delete the post
-/// - Close the `Post` account and return its lamports to the author
+/// - Close the `Post` account and return its lamports to its recorded `payer`
 /// - Verify the PDA is derived using the correct seeds: [POST_SEED, topic, author pubkey]
 /// - Emit a `PostRemoved` event for off-chain indexing
+/// - Close the post's `Poll` account along with it, which must be passed
+///   whenever the post has a poll, and emit a `PollClosed` event
+/// - Drop the post from the author's `Profile` pinned posts, which must be passed
+///   whenever the post is pinned
+/// - Close every `PostChunk` of the post, passed in index order via
+///   `remaining_accounts`, and refund their rent to the post's `payer`
+/// - Close every `PostRevision` of the post, passed in revision number order after
+///   the chunks, and refund their rent to their recorded `payer`, passed after them
+///   unless it is the post's; already pruned revisions are passed as their empty
+///   address. A post re-created at the same address thus starts its revisions over
+/// - Unless the post is a draft, drop a usage from the `Tag` index account of every
+///   hashtag, passed in order after the revisions and before their payers
+/// - Close the post's `PostIndex` along with it, leaving a gap in the global
+///   post sequence
+/// - For co-authored posts, require a majority of the authors to have voted for
+///   removal through `removal_vote`; the author's signature counts as their vote
 ///
 /// The instruction requires the exact `topic` used during post creation to ensure
 /// correct PDA derivation. Account closure is handled automatically via the `close`
@@ -15,30 +31,185 @@
 //-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
 
-use crate::constants::POST_SEED;
-use crate::states::Post;
+use crate::constants::{
+    CHUNK_SEED, CONFIG_SEED, INDEX_SEED, POLL_SEED, POST_SEED, PROFILE_SEED, REVISION_SEED,
+};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Poll, Post, PostChunk, PostIndex, PostRevision, Profile};
+use crate::utils::{close_account, unindex_hashtags, EventEmitter};
 
-pub fn remove_post(ctx: Context<RemovePostContext>) -> Result<()> {
+pub fn remove_post<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemovePostContext<'info>>,
+) -> Result<()> {
     // The account will be automatically closed due to the `close` constraint
 
     let post = &ctx.accounts.post;
     let topic = post.topic.clone();
 
+    require!(
+        post.is_removal_approved(post.removal_votes | 1),
+        DepressError::RemovalNotApproved
+    );
+
+    release_post_accounts(
+        post,
+        ctx.accounts.poll.as_ref(),
+        ctx.accounts.profile.as_mut(),
+        ctx.remaining_accounts,
+        &ctx.accounts.payer.to_account_info(),
+        &mut EventEmitter::new(
+            ctx.accounts.config.as_deref_mut(),
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.bumps.event_authority,
+        ),
+    )?;
+
     // Emit event
-    emit!(PostRemoved {
+    emit_cpi!(PostRemoved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
         post_author: post.post_author,
-        topic: topic,
+        post: post.key(),
+        topic,
     });
 
     Ok(())
 }
 
+/// Releases everything hanging off a post that is about to be closed: drops its
+/// pin from the author's profile, closes its chunks (refunding `rent_receiver`)
+/// and its revisions (refunding their payers), unindexes its hashtags and checks
+/// its poll is closed alongside it.
+///
+/// `accounts` holds the chunks in index order, then the revisions in revision
+/// number order, then the tags of the post's hashtags unless it is a draft, then
+/// the revision payers other than `rent_receiver`.
+pub(crate) fn release_post_accounts<'info>(
+    post: &Account<'info, Post>,
+    poll: Option<&Account<'info, Poll>>,
+    profile: Option<&mut Account<'info, Profile>>,
+    accounts: &[AccountInfo<'info>],
+    rent_receiver: &AccountInfo<'info>,
+    events: &mut EventEmitter,
+) -> Result<()> {
+    // A pinned post is dropped from the author's profile
+    if post.is_pinned {
+        let profile = profile.ok_or(DepressError::ProfileAccountRequired)?;
+        profile.unpin(&post.key())?;
+    }
+
+    // Long-form chunks are closed together with the post
+    let chunk_count = post.chunk_count as usize;
+    require!(
+        accounts.len() >= chunk_count,
+        DepressError::MissingChunkAccount
+    );
+    let (chunks, accounts) = accounts.split_at(chunk_count);
+    for (index, chunk_info) in chunks.iter().enumerate() {
+        require_keys_eq!(
+            *chunk_info.owner,
+            crate::ID,
+            DepressError::InvalidChunkAccount
+        );
+        let chunk = PostChunk::try_deserialize(&mut &chunk_info.try_borrow_data()?[..])?;
+        let chunk_key = Pubkey::create_program_address(
+            &[
+                CHUNK_SEED.as_bytes(),
+                post.key().as_ref(),
+                (index as u32).to_le_bytes().as_ref(),
+                &[chunk.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| DepressError::InvalidChunkAccount)?;
+        require_keys_eq!(
+            chunk_info.key(),
+            chunk_key,
+            DepressError::InvalidChunkAccount
+        );
+
+        close_account(chunk_info, rent_receiver)?;
+    }
+
+    // So are revisions, which would otherwise collide with those of a post
+    // re-created at the same address
+    let revision_count = post.revision_count as usize;
+    require!(
+        accounts.len() >= revision_count,
+        DepressError::MissingRevisionAccount
+    );
+    let (revisions, accounts) = accounts.split_at(revision_count);
+
+    // Drafts never entered the tag index
+    let tag_count = if post.is_draft {
+        0
+    } else {
+        post.hashtags.len()
+    };
+    require!(accounts.len() >= tag_count, DepressError::MissingTagAccount);
+    let (tags, payers) = accounts.split_at(tag_count);
+    unindex_hashtags(&post.hashtags[..tag_count], tags)?;
+
+    for (number, revision_info) in revisions.iter().enumerate() {
+        let (revision_key, _) = Pubkey::find_program_address(
+            &[
+                REVISION_SEED.as_bytes(),
+                post.key().as_ref(),
+                (number as u32).to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            revision_info.key(),
+            revision_key,
+            DepressError::InvalidRevisionAccount
+        );
+        // Already pruned
+        if revision_info.data_is_empty() {
+            continue;
+        }
+
+        require_keys_eq!(
+            *revision_info.owner,
+            crate::ID,
+            DepressError::InvalidRevisionAccount
+        );
+        let revision = PostRevision::try_deserialize(&mut &revision_info.try_borrow_data()?[..])?;
+        let payer = if revision.payer == rent_receiver.key() {
+            rent_receiver
+        } else {
+            payers
+                .iter()
+                .find(|payer| payer.key() == revision.payer)
+                .ok_or(DepressError::MissingRevisionPayer)?
+        };
+        close_account(revision_info, payer)?;
+    }
+
+    // A post's poll is closed together with the post
+    require!(
+        post.has_poll == poll.is_some(),
+        DepressError::PollAccountRequired
+    );
+    if let Some(poll) = poll {
+        events.emit(|header| PollClosed {
+            header,
+            poll: poll.key(),
+            parent_post: post.key(),
+            tallies: poll.tallies.clone(),
+        })?;
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RemovePostContext<'info> {
     #[account(
         mut,
         has_one = post_author,
-        close = post_author,
+        has_one = payer,
+        close = payer,
 
         seeds = [
             POST_SEED.as_bytes(),
@@ -49,13 +220,61 @@ pub struct RemovePostContext<'info> {
     )]
     pub post: Account<'info, Post>,
 
-    #[account(mut)]
     pub post_author: Signer<'info>,
+
+    /// CHECK: Bound to the post through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            POLL_SEED.as_bytes(),
+            post.key().as_ref(),
+        ],
+        bump = poll.bump,
+    )]
+    pub poll: Option<Account<'info, Poll>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            post_author.key().as_ref(),
+        ],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+
+    // Closed together with the post
+    #[account(
+        mut,
+        close = payer,
+        seeds = [INDEX_SEED.as_bytes(), post.global_id.to_le_bytes().as_ref()],
+        bump = post_index.bump,
+    )]
+    pub post_index: Account<'info, PostIndex>,
 }
 
 #[event]
 pub struct PostRemoved {
+    pub header: EventHeader,
     pub post_author: Pubkey,
+    pub post: Pubkey,
     pub topic: String,
 }
+
+#[event]
+pub struct PollClosed {
+    pub header: EventHeader,
+    pub poll: Pubkey,
+    pub parent_post: Pubkey,
+    // Final tallies, one per option
+    pub tallies: Vec<u64>,
+}
@@ -3,9 +3,11 @@
 /// TASK: Implement the "remove reaction from comment" functionality for the DePress program
 ///
 /// Requirements:
-/// - Close the existing reaction account and refund rent to the author
+/// - Close the existing reaction account and refund rent to its recorded `payer`
 /// - Decrement the correct counter (likes/dislikes) on the parent comment
 /// - Ensure the author is the reaction owner (enforced via constraints)
+/// - Accept an ephemeral key signing through a `Session` with `SESSION_REACT`
+///   in place of the wallet, removing the reaction of the session's wallet
 /// - Prevent underflow by validating counters are > 0
 /// - Emit a `ReactionRemoved` event for indexing
 ///
@@ -14,11 +16,22 @@
 //-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
 
-use crate::constants::COMMENT_REACTION_SEED;
+use crate::constants::{COMMENT_REACTION_SEED, CONFIG_SEED, SESSION_REACT, SESSION_SEED};
 use crate::errors::DepressError;
-use crate::states::{Comment, ReactionComment, ReactionType};
+use crate::states::{Comment, Config, EventHeader, ReactionComment, ReactionType, Session};
 
 pub fn remove_reaction_comment(ctx: Context<RemoveReactionCommentContext>) -> Result<()> {
+    let reaction_author = Session::authorize(
+        ctx.accounts.reaction_author.key(),
+        ctx.accounts.session.as_deref(),
+        SESSION_REACT,
+        Clock::get()?.unix_timestamp,
+    )?;
+    require_keys_eq!(
+        ctx.accounts.comment_reaction.reaction_author,
+        reaction_author,
+        DepressError::InvalidOwner
+    );
     let comment_reaction = &ctx.accounts.comment_reaction.reaction;
     let comment = &mut ctx.accounts.comment;
 
@@ -35,27 +48,27 @@ pub fn remove_reaction_comment(ctx: Context<RemoveReactionCommentContext>) -> Re
     }
 
     // Emit event before account is closed
-    emit!(ReactionCommentRemoved {
-        author: ctx.accounts.reaction_author.key(),
+    emit_cpi!(ReactionCommentRemoved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        author: reaction_author,
         parent_comment: comment.key(),
-        reaction: match comment_reaction {
-            ReactionType::Like => 0,
-            ReactionType::Dislike => 1,
-        },
+        parent_post: comment.parent_post,
+        reaction: comment_reaction.clone(),
     });
 
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RemoveReactionCommentContext<'info> {
     #[account(
         mut,
-        has_one = reaction_author @ DepressError::InvalidOwner,
-        close = reaction_author,
+        has_one = payer,
+        close = payer,
         seeds = [
             COMMENT_REACTION_SEED.as_bytes(),
-            reaction_author.key().as_ref(),
+            &Session::author_seed(reaction_author.key(), session.as_deref()),
             comment.key().as_ref(),
         ],
         bump = comment_reaction.bump,
@@ -65,16 +78,35 @@ pub struct RemoveReactionCommentContext<'info> {
     #[account(mut)]
     pub comment: Account<'info, Comment>,
 
-    #[account(mut)]
+    // The wallet, or a session key acting for it
     pub reaction_author: Signer<'info>,
 
+    /// CHECK: Bound to the reaction through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            session.authority.as_ref(),
+            reaction_author.key().as_ref(),
+        ],
+        bump = session.bump,
+    )]
+    pub session: Option<Account<'info, Session>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
 }
 
 #[event]
 pub struct ReactionCommentRemoved {
+    pub header: EventHeader,
     pub author: Pubkey,
     pub parent_comment: Pubkey,
-    // 0 = Like, 1 = Dislike
-    pub reaction: u8,
+    pub parent_post: Pubkey,
+    pub reaction: ReactionType,
 }
@@ -4,9 +4,11 @@
 ///
 /// Requirements:
 /// - Verify the reaction exists and belongs to the reaction author (enforced via PDA + has_one)
+/// - Accept an ephemeral key signing through a `Session` with `SESSION_REACT`
+///   in place of the wallet, removing the reaction of the session's wallet
 /// - Decrement the appropriate counter (likes/dislikes) on the parent post
 /// - Prevent underflow by ensuring counters are > 0
-/// - Close the reaction account and refund rent to the author
+/// - Close the reaction account and refund rent to its recorded `payer`
 /// - Emit a `ReactionPostRemoved` event for off-chain indexing
 ///
 /// The PDA constraint and `has_one` ensure only the true author can remove their reaction.
@@ -14,12 +16,23 @@
 //-------------------------------------------------------------------------------
 use anchor_lang::prelude::*;
 
-use crate::constants::POST_REACTION_SEED;
+use crate::constants::{CONFIG_SEED, POST_REACTION_SEED, SESSION_REACT, SESSION_SEED};
 use crate::errors::DepressError;
-use crate::states::{Post, ReactionPost, ReactionType};
+use crate::states::{Config, EventHeader, Post, ReactionPost, ReactionType, Session};
 
 pub fn remove_reaction_post(ctx: Context<RemoveReactionPostContext>) -> Result<()> {
+    let reaction_author = Session::authorize(
+        ctx.accounts.reaction_author.key(),
+        ctx.accounts.session.as_deref(),
+        SESSION_REACT,
+        Clock::get()?.unix_timestamp,
+    )?;
     let reaction = &ctx.accounts.post_reaction;
+    require_keys_eq!(
+        reaction.reaction_author,
+        reaction_author,
+        DepressError::InvalidOwner
+    );
     let post = &mut ctx.accounts.post;
 
     // Safely decrement the appropriate counter
@@ -35,27 +48,26 @@ pub fn remove_reaction_post(ctx: Context<RemoveReactionPostContext>) -> Result<(
     }
 
     // Emit event before account is closed
-    emit!(ReactionPostRemoved {
-        author: ctx.accounts.reaction_author.key(),
+    emit_cpi!(ReactionPostRemoved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        author: reaction_author,
         parent_post: post.key(),
-        reaction: match reaction.reaction {
-            ReactionType::Like => 0,
-            ReactionType::Dislike => 1,
-        },
+        reaction: reaction.reaction.clone(),
     });
 
     Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RemoveReactionPostContext<'info> {
     #[account(
         mut,
-        has_one = reaction_author @ DepressError::InvalidOwner,
-        close = reaction_author,
+        has_one = payer,
+        close = payer,
         seeds = [
             POST_REACTION_SEED.as_bytes(),
-            reaction_author.key().as_ref(),
+            &Session::author_seed(reaction_author.key(), session.as_deref()),
             post.key().as_ref(),
         ],
         bump = post_reaction.bump,
@@ -65,16 +77,34 @@ pub struct RemoveReactionPostContext<'info> {
     #[account(mut)]
     pub post: Account<'info, Post>,
 
-    #[account(mut)]
+    // The wallet, or a session key acting for it
     pub reaction_author: Signer<'info>,
 
+    /// CHECK: Bound to the reaction through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
+
+    #[account(
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            session.authority.as_ref(),
+            reaction_author.key().as_ref(),
+        ],
+        bump = session.bump,
+    )]
+    pub session: Option<Account<'info, Session>>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
 }
 
 #[event]
 pub struct ReactionPostRemoved {
+    pub header: EventHeader,
     pub author: Pubkey,
     pub parent_post: Pubkey,
-    // 0 = Like, 1 = Dislike
-    pub reaction: u8,
+    pub reaction: ReactionType,
 }
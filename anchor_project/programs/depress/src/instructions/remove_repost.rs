@@ -0,0 +1,147 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "remove repost" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the reposter may undo their repost
+/// - Close the `Repost` account and refund rent to its recorded `payer`
+/// - Decrement the `repost_count` on the original post if it still exists and is
+///   the reposted one, not a post re-created at its address
+/// - Emit a `RepostRemoved` event for off-chain indexing
+///
+/// `repost_remove_orphaned` is the cleanup path for reposts whose original post
+/// has been removed, possibly re-created since: anyone may close them, and the
+/// rent goes back to the recorded `payer`, who funded the repost.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, REPOST_SEED};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post, Repost};
+
+pub fn remove_repost(ctx: Context<RemoveRepostContext>) -> Result<()> {
+    let repost = &ctx.accounts.repost;
+    let original_post = &ctx.accounts.original_post;
+
+    // The original post may already be gone, only update it while it exists
+    if original_post.owner == &crate::ID && !original_post.data_is_empty() {
+        let mut data = original_post.try_borrow_mut_data()?;
+        let mut post = Post::try_deserialize(&mut &data[..])?;
+        if repost.reposts(&post) {
+            require!(post.repost_count > 0, DepressError::InvalidReactionState);
+            post.repost_count -= 1;
+            post.try_serialize(&mut &mut data[..])?;
+        }
+    }
+
+    // Emit event before account is closed
+    emit_cpi!(RepostRemoved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        reposter: repost.reposter,
+        original_post: repost.original_post,
+    });
+
+    Ok(())
+}
+
+pub fn remove_orphaned_repost(ctx: Context<RemoveOrphanedRepostContext>) -> Result<()> {
+    let repost = &ctx.accounts.repost;
+    let original_post = &ctx.accounts.original_post;
+
+    // A post at the original address must have been re-created since
+    if !original_post.data_is_empty() {
+        require_keys_eq!(
+            *original_post.owner,
+            crate::ID,
+            DepressError::InvalidPostAccount
+        );
+        let post = Post::try_deserialize(&mut &original_post.try_borrow_data()?[..])?;
+        require!(!repost.reposts(&post), DepressError::OriginalPostExists);
+    }
+
+    emit_cpi!(RepostRemoved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        reposter: repost.reposter,
+        original_post: repost.original_post,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveRepostContext<'info> {
+    #[account(
+        mut,
+        has_one = reposter @ DepressError::InvalidOwner,
+        has_one = original_post,
+        has_one = payer,
+        close = payer,
+        seeds = [
+            REPOST_SEED.as_bytes(),
+            reposter.key().as_ref(),
+            original_post.key().as_ref(),
+        ],
+        bump = repost.bump,
+    )]
+    pub repost: Account<'info, Repost>,
+
+    /// CHECK: Bound to the repost through `has_one`; may already be closed.
+    #[account(mut)]
+    pub original_post: UncheckedAccount<'info>,
+
+    pub reposter: Signer<'info>,
+
+    /// CHECK: Bound to the repost through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveOrphanedRepostContext<'info> {
+    #[account(
+        mut,
+        has_one = reposter @ DepressError::InvalidOwner,
+        has_one = original_post,
+        has_one = payer,
+        close = payer,
+        seeds = [
+            REPOST_SEED.as_bytes(),
+            reposter.key().as_ref(),
+            original_post.key().as_ref(),
+        ],
+        bump = repost.bump,
+    )]
+    pub repost: Account<'info, Repost>,
+
+    /// CHECK: Must be closed or hold another post, which is verified by the handler.
+    pub original_post: UncheckedAccount<'info>,
+
+    /// CHECK: Bound to the repost through `has_one`.
+    pub reposter: UncheckedAccount<'info>,
+
+    /// CHECK: Bound to the repost through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct RepostRemoved {
+    pub header: EventHeader,
+    pub reposter: Pubkey,
+    pub original_post: Pubkey,
+}
@@ -0,0 +1,65 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "revoke session" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the wallet that created the session may revoke it, at any time
+/// - Close the `Session` account and refund rent to its recorded `payer`
+/// - Emit a `SessionRevoked` event
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, SESSION_SEED};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Session};
+
+pub fn revoke_session(ctx: Context<RevokeSessionContext>) -> Result<()> {
+    // The account will be automatically closed due to the `close` constraint
+
+    let session = &ctx.accounts.session;
+
+    // Emit event
+    emit_cpi!(SessionRevoked {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        authority: session.authority,
+        session_key: session.session_key,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeSessionContext<'info> {
+    #[account(
+        mut,
+        has_one = authority @ DepressError::InvalidOwner,
+        has_one = payer,
+        close = payer,
+        seeds = [
+            SESSION_SEED.as_bytes(),
+            authority.key().as_ref(),
+            session.session_key.as_ref(),
+        ],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Bound to the session through `has_one`; receives the reclaimed rent.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub header: EventHeader,
+    pub authority: Pubkey,
+    pub session_key: Pubkey,
+}
@@ -0,0 +1,86 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "save draft" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may save the draft, and only while it is a draft that
+///   is not finalized
+/// - Enforce that the new `content` does not exceed the maximum allowed length
+/// - When a `content_ref` is given, validate it and replace the post's reference
+///   with it; the inline content must stay empty while the post has a reference
+/// - Overwrite the content in place, no `PostRevision` is written for drafts
+/// - Re-extract hashtags without recording them in the tag index yet
+/// - Replace the content labels when new ones are given
+/// - Emit a `DraftSaved` event
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, CONTENT_LENGTH};
+use crate::errors::DepressError;
+use crate::instructions::DraftSaved;
+use crate::states::{Config, ContentLabels, ContentRef, EventHeader, Post, Tag};
+
+pub fn save_draft(
+    ctx: Context<SaveDraftContext>,
+    post_content: String,
+    labels: Option<ContentLabels>,
+    content_ref: Option<ContentRef>,
+) -> Result<()> {
+    // Validate content length
+    require!(
+        post_content.len() <= CONTENT_LENGTH,
+        DepressError::ContentTooLong
+    );
+
+    if let Some(labels) = &labels {
+        labels.validate()?;
+    }
+    if let Some(content_ref) = &content_ref {
+        content_ref.validate()?;
+    }
+
+    let hashtags = Tag::parse_hashtags(&post_content)?;
+
+    let post = &mut ctx.accounts.post;
+    require!(post.is_draft, DepressError::PostNotDraft);
+    require!(!post.finalized, DepressError::PostFinalized);
+    require!(
+        (post.content_ref.is_none() && content_ref.is_none()) || post_content.is_empty(),
+        DepressError::InlineContentNotAllowed
+    );
+
+    post.total_length = post.total_length - post.content.len() as u32 + post_content.len() as u32;
+    post.content = post_content;
+    if content_ref.is_some() {
+        post.content_ref = content_ref;
+    }
+    post.hashtags = hashtags;
+    if let Some(labels) = labels {
+        post.labels = labels;
+    }
+
+    // Emit event
+    emit_cpi!(DraftSaved {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post_author: post.post_author,
+        post: post.key(),
+        topic: post.topic.clone(),
+        saved_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SaveDraftContext<'info> {
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
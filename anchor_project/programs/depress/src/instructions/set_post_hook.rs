@@ -0,0 +1,57 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "set post hook" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may set or clear the post's hook program
+/// - Reject this program itself as hook program
+/// - Once set, `comment_add`, `comment_add_relayed` and the reaction instructions
+///   invoke the hook with the `Engagement`, and fail when the hook fails
+/// - Emit a `PostHookSet` event
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post};
+
+pub fn set_post_hook(ctx: Context<SetPostHookContext>, hook_program: Option<Pubkey>) -> Result<()> {
+    require!(
+        hook_program != Some(crate::ID),
+        DepressError::InvalidHookProgram
+    );
+
+    let post = &mut ctx.accounts.post;
+    post.hook_program = hook_program;
+
+    // Emit event
+    emit_cpi!(PostHookSet {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post: post.key(),
+        hook_program,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPostHookContext<'info> {
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PostHookSet {
+    pub header: EventHeader,
+    pub post: Pubkey,
+    // `None` when the hook was cleared
+    pub hook_program: Option<Pubkey>,
+}
@@ -0,0 +1,109 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "tip post" functionality for the DePress program
+///
+/// Requirements:
+/// - Anyone may tip a published post a positive amount of lamports
+/// - Split the tip between the co-authors by their `share_bps`, passed in order
+///   via `remaining_accounts`, and send the remainder to `post_author`
+/// - Reject tips leaving a recipient below the rent-exempt minimum, which the
+///   runtime would otherwise fail on with an opaque error
+/// - Emit a `PostTipped` event for off-chain indexing
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::{CONFIG_SEED, SHARE_BASIS_POINTS};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post};
+
+pub fn tip_post<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TipPostContext<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let post = &ctx.accounts.post;
+    require!(amount > 0, DepressError::InvalidTipAmount);
+    require!(
+        post.is_published_at(Clock::get()?.unix_timestamp),
+        DepressError::PostNotPublished
+    );
+    require!(
+        ctx.remaining_accounts.len() >= post.co_authors.len(),
+        DepressError::MissingCoAuthorAccount
+    );
+
+    let tipper = ctx.accounts.tipper.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let rent = Rent::get()?;
+    let pay = |to: &AccountInfo<'info>, lamports: u64| -> Result<()> {
+        if lamports == 0 {
+            return Ok(());
+        }
+        require!(
+            rent.is_exempt(to.lamports().saturating_add(lamports), to.data_len()),
+            DepressError::TipBelowRent
+        );
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: tipper.clone(),
+                    to: to.clone(),
+                },
+            ),
+            lamports,
+        )
+    };
+
+    let mut remainder = amount;
+    for (co_author, account) in post.co_authors.iter().zip(ctx.remaining_accounts) {
+        require_keys_eq!(
+            account.key(),
+            co_author.author,
+            DepressError::InvalidCoAuthorAccount
+        );
+        let share =
+            (amount as u128 * co_author.share_bps as u128 / SHARE_BASIS_POINTS as u128) as u64;
+        pay(account, share)?;
+        remainder -= share;
+    }
+    pay(&ctx.accounts.post_author.to_account_info(), remainder)?;
+
+    // Emit event
+    emit_cpi!(PostTipped {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post: post.key(),
+        tipper: tipper.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TipPostContext<'info> {
+    #[account(has_one = post_author)]
+    pub post: Account<'info, Post>,
+
+    /// CHECK: Bound to the post through `has_one`; receives its share of the tip.
+    #[account(mut)]
+    pub post_author: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PostTipped {
+    pub header: EventHeader,
+    pub post: Pubkey,
+    pub tipper: Pubkey,
+    pub amount: u64,
+}
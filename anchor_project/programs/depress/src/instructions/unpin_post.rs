@@ -0,0 +1,66 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "unpin post" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author may unpin their post
+/// - Remove the post from the author's `Profile` pinned posts
+/// - Clear the post's pinned flag
+/// - Emit a `PostUnpinned` event for off-chain indexing
+///
+/// Pins are also dropped automatically when the post is removed via `post_remove`.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, PROFILE_SEED};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post, Profile};
+
+pub fn unpin_post(ctx: Context<UnpinPostContext>) -> Result<()> {
+    let profile = &mut ctx.accounts.profile;
+    let post = &mut ctx.accounts.post;
+
+    require!(post.is_pinned, DepressError::PostNotPinned);
+    profile.unpin(&post.key())?;
+    post.is_pinned = false;
+
+    // Emit event
+    emit_cpi!(PostUnpinned {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post_author: profile.author,
+        post: post.key(),
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnpinPostContext<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PROFILE_SEED.as_bytes(),
+            post_author.key().as_ref()
+        ],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(mut, has_one = post_author @ DepressError::InvalidOwner)]
+    pub post: Account<'info, Post>,
+
+    pub post_author: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PostUnpinned {
+    pub header: EventHeader,
+    pub post_author: Pubkey,
+    pub post: Pubkey,
+}
@@ -0,0 +1,112 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "vote on poll" functionality for the DePress program
+///
+/// Requirements:
+/// - Reject votes while the poll's post, passed as `post`, is not published or once
+///   it has expired
+/// - Reject votes once the poll's closing time has passed
+/// - Reject option indexes outside the poll's options
+/// - Initialize a new `PollVote` account as a PDA using seeds:
+///   [POLL_VOTE_SEED, voter pubkey, poll pubkey]
+///   recording the `global_id` of the post, so votes on a poll removed with its
+///   post can be closed through `poll_vote_remove_orphaned`
+/// - Increment the tally of the chosen option on the poll
+/// - Prevent duplicate votes via PDA uniqueness (one vote per user per poll)
+/// - Emit a `PollVoted` event for every vote
+/// - Charge the rent to `payer`, which may differ from the voter, and record it
+///   on the account so the rent goes back to it on close
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, DISCRIMINATOR, POLL_VOTE_SEED, POLL_VOTE_VERSION};
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Poll, PollVote, Post};
+
+pub fn vote_poll(ctx: Context<VotePollContext>, option_index: u8) -> Result<()> {
+    let poll = &mut ctx.accounts.poll;
+    let post = &ctx.accounts.post;
+    let now = Clock::get()?.unix_timestamp;
+    require!(post.is_published_at(now), DepressError::PostNotPublished);
+    require!(!post.is_expired_at(now), DepressError::PostExpired);
+
+    // Validate the poll is still open
+    if let Some(closes_at) = poll.closes_at {
+        require!(now < closes_at, DepressError::PollClosed);
+    }
+
+    // Validate the chosen option
+    let index = option_index as usize;
+    require!(index < poll.tallies.len(), DepressError::InvalidPollOption);
+
+    // Initialize the vote account
+    let poll_vote = &mut ctx.accounts.poll_vote;
+    poll_vote.version = POLL_VOTE_VERSION;
+    poll_vote.voter = ctx.accounts.voter.key();
+    poll_vote.payer = ctx.accounts.payer.key();
+    poll_vote.poll = poll.key();
+    poll_vote.post_global_id = post.global_id;
+    poll_vote.option_index = option_index;
+    poll_vote.bump = ctx.bumps.poll_vote;
+
+    // Update the tally
+    poll.tallies[index] += 1;
+
+    // Emit event
+    emit_cpi!(PollVoted {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        voter: poll_vote.voter,
+        poll: poll.key(),
+        parent_post: poll.parent_post,
+        option_index,
+        tally: poll.tallies[index],
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VotePollContext<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR + PollVote::INIT_SPACE,
+        seeds = [
+            POLL_VOTE_SEED.as_bytes(),
+            voter.key().as_ref(),
+            poll.key().as_ref()
+        ],
+        bump
+    )]
+    pub poll_vote: Account<'info, PollVote>,
+
+    #[account(mut)]
+    pub poll: Account<'info, Poll>,
+
+    #[account(address = poll.parent_post @ DepressError::InvalidPostAccount)]
+    pub post: Account<'info, Post>,
+
+    pub voter: Signer<'info>,
+
+    // Funds the new accounts, the author unless sponsored
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct PollVoted {
+    pub header: EventHeader,
+    pub voter: Pubkey,
+    pub poll: Pubkey,
+    pub parent_post: Pubkey,
+    pub option_index: u8,
+    // Tally of the chosen option after this vote
+    pub tally: u64,
+}
@@ -0,0 +1,67 @@
+//-------------------------------------------------------------------------------
+///
+/// TASK: Implement the "vote removal" functionality for the DePress program
+///
+/// Requirements:
+/// - Only the post author or one of its co-authors may vote, once each
+/// - Record the vote in the post's `removal_votes`
+/// - Emit a `RemovalVoted` event with the current and required number of votes
+///
+/// The first vote acts as the removal proposal. Once a strict majority of the
+/// authors voted, anyone may close the post through `post_remove_voted`.
+///
+//-------------------------------------------------------------------------------
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::errors::DepressError;
+use crate::states::{Config, EventHeader, Post};
+
+pub fn vote_removal(ctx: Context<VoteRemovalContext>) -> Result<()> {
+    let post = &mut ctx.accounts.post;
+    let voter = ctx.accounts.voter.key();
+
+    let index = post
+        .author_index(&voter)
+        .ok_or(DepressError::NotPostAuthor)?;
+    let vote = 1u8 << index;
+    require!(
+        post.removal_votes & vote == 0,
+        DepressError::RemovalAlreadyVoted
+    );
+    post.removal_votes |= vote;
+    let authors = post.co_authors.len() as u8 + 1;
+
+    // Emit event
+    emit_cpi!(RemovalVoted {
+        header: EventHeader::next(ctx.accounts.config.as_deref_mut())?,
+        post: post.key(),
+        voter,
+        votes: post.removal_votes.count_ones() as u8,
+        required: authors / 2 + 1,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VoteRemovalContext<'info> {
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+
+    pub voter: Signer<'info>,
+
+    // Numbers the emitted events, which are unsequenced without it
+    #[account(mut, seeds = [CONFIG_SEED.as_bytes()], bump = config.bump)]
+    pub config: Option<Account<'info, Config>>,
+}
+
+#[event]
+pub struct RemovalVoted {
+    pub header: EventHeader,
+    pub post: Pubkey,
+    pub voter: Pubkey,
+    pub votes: u8,
+    pub required: u8,
+}
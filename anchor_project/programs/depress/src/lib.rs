@@ -1,14 +1,23 @@
 #![allow(deprecated)]
 #![allow(unexpected_cfgs)]
+// The generated CPI helpers mirror the instruction arguments
+#![allow(clippy::too_many_arguments)]
 use anchor_lang::prelude::*;
 
+#[cfg(not(target_os = "solana"))]
+pub mod client;
 pub mod constants;
 pub mod errors;
 pub mod instructions;
 pub mod states;
+pub mod utils;
 
 use instructions::*;
-use states::reaction::ReactionType;
+use states::attachment::Attachment;
+use states::co_author::CoAuthor;
+use states::content_ref::ContentRef;
+use states::labels::ContentLabels;
+use states::reaction::{BatchReaction, ReactionType};
 
 declare_id!("5aQmhcFhVmgtmCdGtffRuMYL9R1WsARAtukzxUttPKKN");
 
@@ -21,23 +30,161 @@ pub mod depress {
     //     Ok(())
     // }
 
-    pub fn post_add(
-        ctx: Context<AddPostContext>,
+    pub fn config_initialize(ctx: Context<InitializeConfigContext>) -> Result<()> {
+        initialize_config(ctx)
+    }
+
+    pub fn account_migrate(ctx: Context<MigrateAccountContext>) -> Result<()> {
+        migrate_account(ctx)
+    }
+
+    pub fn post_add<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddPostContext<'info>>,
         post_topic: String,
         post_content: String,
+        mentions: Vec<Pubkey>,
+        content_ref: Option<ContentRef>,
+        attachments: Vec<Attachment>,
+        labels: ContentLabels,
+        publish_at: Option<i64>,
+        expires_at: Option<i64>,
+        draft: bool,
+        co_authors: Vec<CoAuthor>,
     ) -> Result<()> {
-        add_post(ctx, post_topic, post_content)
+        add_post(
+            ctx,
+            post_topic,
+            post_content,
+            mentions,
+            content_ref,
+            attachments,
+            labels,
+            publish_at,
+            expires_at,
+            draft,
+            co_authors,
+        )
     }
 
-    pub fn post_remove(ctx: Context<RemovePostContext>) -> Result<()> {
+    pub fn post_remove<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemovePostContext<'info>>,
+    ) -> Result<()> {
         remove_post(ctx)
     }
 
-    pub fn like_post(ctx: Context<AddReactionPostContext>) -> Result<()> {
+    pub fn post_edit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EditPostContext<'info>>,
+        post_content: String,
+        labels: Option<ContentLabels>,
+        content_ref: Option<ContentRef>,
+    ) -> Result<()> {
+        edit_post(ctx, post_content, labels, content_ref)
+    }
+
+    pub fn publish_due(ctx: Context<PublishDueContext>) -> Result<()> {
+        instructions::publish_due(ctx)
+    }
+
+    pub fn close_expired_post<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseExpiredPostContext<'info>>,
+    ) -> Result<()> {
+        instructions::close_expired_post(ctx)
+    }
+
+    pub fn draft_save(
+        ctx: Context<SaveDraftContext>,
+        post_content: String,
+        labels: Option<ContentLabels>,
+        content_ref: Option<ContentRef>,
+    ) -> Result<()> {
+        save_draft(ctx, post_content, labels, content_ref)
+    }
+
+    pub fn draft_publish<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PublishDraftContext<'info>>,
+    ) -> Result<()> {
+        publish_draft(ctx)
+    }
+
+    pub fn co_author_approve(ctx: Context<ApproveCoAuthorContext>) -> Result<()> {
+        approve_co_author(ctx)
+    }
+
+    pub fn removal_vote(ctx: Context<VoteRemovalContext>) -> Result<()> {
+        vote_removal(ctx)
+    }
+
+    pub fn post_remove_voted<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveVotedPostContext<'info>>,
+    ) -> Result<()> {
+        remove_voted_post(ctx)
+    }
+
+    pub fn post_tip<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TipPostContext<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        tip_post(ctx, amount)
+    }
+
+    pub fn post_set_hook(
+        ctx: Context<SetPostHookContext>,
+        hook_program: Option<Pubkey>,
+    ) -> Result<()> {
+        set_post_hook(ctx, hook_program)
+    }
+
+    pub fn post_add_relayed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddRelayedPostContext<'info>>,
+        post_topic: String,
+        post_content: String,
+        nonce: u64,
+    ) -> Result<()> {
+        add_relayed_post(ctx, post_topic, post_content, nonce)
+    }
+
+    pub fn comment_add_relayed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddRelayedCommentContext<'info>>,
+        comment_content: String,
+        nonce: u64,
+    ) -> Result<()> {
+        add_relayed_comment(ctx, comment_content, nonce)
+    }
+
+    pub fn session_create(
+        ctx: Context<CreateSessionContext>,
+        expires_at: i64,
+        permissions: u8,
+        top_up: u64,
+    ) -> Result<()> {
+        create_session(ctx, expires_at, permissions, top_up)
+    }
+
+    pub fn session_revoke(ctx: Context<RevokeSessionContext>) -> Result<()> {
+        revoke_session(ctx)
+    }
+
+    pub fn revision_prune(ctx: Context<PruneRevisionContext>) -> Result<()> {
+        prune_revision(ctx)
+    }
+
+    pub fn chunk_append(ctx: Context<AppendChunkContext>, chunk_content: String) -> Result<()> {
+        append_chunk(ctx, chunk_content)
+    }
+
+    pub fn post_finalize(ctx: Context<FinalizePostContext>) -> Result<()> {
+        finalize_post(ctx)
+    }
+
+    pub fn like_post<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddReactionPostContext<'info>>,
+    ) -> Result<()> {
         add_reaction_post(ctx, ReactionType::Like)
     }
 
-    pub fn dislike_post(ctx: Context<AddReactionPostContext>) -> Result<()> {
+    pub fn dislike_post<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddReactionPostContext<'info>>,
+    ) -> Result<()> {
         add_reaction_post(ctx, ReactionType::Dislike)
     }
 
@@ -45,22 +192,82 @@ pub mod depress {
         remove_reaction_post(ctx)
     }
 
-    pub fn comment_add(ctx: Context<AddCommentContext>, comment_content: String) -> Result<()> {
-        add_comment(ctx, comment_content)
+    pub fn comment_add<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddCommentContext<'info>>,
+        comment_content: String,
+        mentions: Vec<Pubkey>,
+        attachment: Option<Attachment>,
+        labels: ContentLabels,
+    ) -> Result<()> {
+        add_comment(ctx, comment_content, mentions, attachment, labels)
+    }
+
+    pub fn comment_labels_edit(
+        ctx: Context<EditCommentLabelsContext>,
+        labels: ContentLabels,
+    ) -> Result<()> {
+        edit_comment_labels(ctx, labels)
     }
     pub fn comment_remove(ctx: Context<RemoveCommentContext>) -> Result<()> {
         remove_comment(ctx)
     }
 
-    pub fn like_comment(ctx: Context<AddReactionCommentContext>) -> Result<()> {
+    pub fn like_comment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddReactionCommentContext<'info>>,
+    ) -> Result<()> {
         add_reaction_comment(ctx, ReactionType::Like)
     }
 
-    pub fn dislike_comment(ctx: Context<AddReactionCommentContext>) -> Result<()> {
+    pub fn dislike_comment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddReactionCommentContext<'info>>,
+    ) -> Result<()> {
         add_reaction_comment(ctx, ReactionType::Dislike)
     }
 
     pub fn reaction_remove_comment(ctx: Context<RemoveReactionCommentContext>) -> Result<()> {
         remove_reaction_comment(ctx)
     }
+
+    pub fn reaction_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchReactContext<'info>>,
+        reactions: Vec<BatchReaction>,
+    ) -> Result<()> {
+        batch_react(ctx, reactions)
+    }
+
+    pub fn repost_add(ctx: Context<AddRepostContext>) -> Result<()> {
+        add_repost(ctx)
+    }
+
+    pub fn repost_remove(ctx: Context<RemoveRepostContext>) -> Result<()> {
+        remove_repost(ctx)
+    }
+
+    pub fn repost_remove_orphaned(ctx: Context<RemoveOrphanedRepostContext>) -> Result<()> {
+        remove_orphaned_repost(ctx)
+    }
+
+    pub fn poll_add(
+        ctx: Context<AddPollContext>,
+        options: Vec<String>,
+        closes_at: Option<i64>,
+    ) -> Result<()> {
+        add_poll(ctx, options, closes_at)
+    }
+
+    pub fn poll_vote(ctx: Context<VotePollContext>, option_index: u8) -> Result<()> {
+        vote_poll(ctx, option_index)
+    }
+
+    pub fn poll_vote_remove_orphaned(ctx: Context<RemoveOrphanedPollVoteContext>) -> Result<()> {
+        remove_orphaned_poll_vote(ctx)
+    }
+
+    pub fn pin_post(ctx: Context<PinPostContext>) -> Result<()> {
+        instructions::pin_post(ctx)
+    }
+
+    pub fn unpin_post(ctx: Context<UnpinPostContext>) -> Result<()> {
+        instructions::unpin_post(ctx)
+    }
 }
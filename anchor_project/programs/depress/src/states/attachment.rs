@@ -0,0 +1,31 @@
+use crate::constants::{ALT_TEXT_LENGTH, MIME_TYPE_LENGTH, URI_LENGTH};
+use crate::errors::DepressError;
+use crate::states::content_ref::{validate_mime_type, validate_uri};
+use anchor_lang::prelude::*;
+
+/// Media file (image, video, document) attached to a post or comment.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
+pub struct Attachment {
+    #[max_len(URI_LENGTH)]
+    pub uri: String,
+    #[max_len(MIME_TYPE_LENGTH)]
+    pub mime_type: String,
+    pub byte_size: u64,
+    // SHA-256 of the file
+    pub content_hash: [u8; 32],
+    #[max_len(ALT_TEXT_LENGTH)]
+    pub alt_text: String,
+}
+
+impl Attachment {
+    pub fn validate(&self) -> Result<()> {
+        validate_uri(&self.uri)?;
+        validate_mime_type(&self.mime_type)?;
+        require!(self.byte_size > 0, DepressError::InvalidContentLength);
+        require!(
+            self.alt_text.len() <= ALT_TEXT_LENGTH,
+            DepressError::AltTextTooLong
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+use crate::constants::CHUNK_LENGTH;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PostChunk {
+    // Layout version, `CHUNK_VERSION` when written
+    pub version: u8,
+    pub post: Pubkey,
+    pub index: u32,
+    #[max_len(CHUNK_LENGTH)]
+    pub content: String,
+    pub bump: u8,
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// Additional author of a post, entitled to `share_bps` of its tips.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
+pub struct CoAuthor {
+    pub author: Pubkey,
+    // Share of tips in basis points, the post author keeps the remainder
+    pub share_bps: u16,
+    // Set once the co-author signed `post_add` or ran `co_author_approve`
+    pub approved: bool,
+}
@@ -1,14 +1,25 @@
-use crate::constants::COMMENT_LENGTH;
+use crate::constants::{COMMENT_DISCRIMINATOR, COMMENT_LENGTH, MAX_MENTIONS};
+use crate::states::{Attachment, ContentLabels};
 use anchor_lang::prelude::*;
 
-#[account]
+/// Fixed-size fields come first, at the `COMMENT_*_OFFSET` offsets, so clients
+/// can match them with `memcmp` filters; variable-size fields follow.
+#[account(discriminator = COMMENT_DISCRIMINATOR)]
 #[derive(InitSpace)]
 pub struct Comment {
+    // Layout version, `COMMENT_VERSION` when written
+    pub version: u8,
     pub comment_author: Pubkey,
+    // Funded the account, refunded on close
+    pub payer: Pubkey,
     pub parent_post: Pubkey,
-    #[max_len(COMMENT_LENGTH)]
-    pub content: String,
     pub likes: u64,
     pub dislikes: u64,
     pub bump: u8,
+    #[max_len(COMMENT_LENGTH)]
+    pub content: String,
+    #[max_len(MAX_MENTIONS)]
+    pub mentions: Vec<Pubkey>,
+    pub attachment: Option<Attachment>,
+    pub labels: ContentLabels,
 }
@@ -0,0 +1,38 @@
+use crate::constants::EVENT_SCHEMA_VERSION;
+use crate::states::EventHeader;
+use anchor_lang::prelude::*;
+
+/// Program-wide singleton, created once by `config_initialize`.
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    // Layout version, `CONFIG_VERSION` when written
+    pub version: u8,
+    // Sequence number of the last emitted event
+    pub event_sequence: u64,
+    // Number of posts created so far, and thereby the id of the next one
+    pub post_count: u64,
+    pub bump: u8,
+}
+
+impl Config {
+    /// Header for the next emitted event, taking the next global sequence number.
+    pub fn next_header(&mut self) -> Result<EventHeader> {
+        let clock = Clock::get()?;
+        self.event_sequence += 1;
+
+        Ok(EventHeader {
+            version: EVENT_SCHEMA_VERSION,
+            sequence: self.event_sequence,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        })
+    }
+
+    /// Takes the `global_id` of a new post.
+    pub fn next_post_id(&mut self) -> u64 {
+        let id = self.post_count;
+        self.post_count += 1;
+        id
+    }
+}
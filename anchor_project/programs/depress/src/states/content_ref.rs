@@ -0,0 +1,62 @@
+use crate::constants::{MIME_TYPE_LENGTH, URI_LENGTH, URI_SCHEMES};
+use crate::errors::DepressError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Commitment to content stored off-chain (Arweave, IPFS or HTTP).
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, InitSpace)]
+pub struct ContentRef {
+    #[max_len(URI_LENGTH)]
+    pub uri: String,
+    // SHA-256 of the content body
+    pub content_hash: [u8; 32],
+    #[max_len(MIME_TYPE_LENGTH)]
+    pub mime_type: String,
+    pub byte_length: u64,
+}
+
+impl ContentRef {
+    pub fn validate(&self) -> Result<()> {
+        validate_uri(&self.uri)?;
+        validate_mime_type(&self.mime_type)?;
+        require!(self.byte_length > 0, DepressError::InvalidContentLength);
+        Ok(())
+    }
+
+    /// Checks that `body` matches the committed length and SHA-256 hash.
+    pub fn verify(&self, body: &[u8]) -> bool {
+        body.len() as u64 == self.byte_length && hash(body).to_bytes() == self.content_hash
+    }
+}
+
+pub(crate) fn validate_uri(uri: &str) -> Result<()> {
+    require!(uri.len() <= URI_LENGTH, DepressError::UriTooLong);
+    require!(
+        URI_SCHEMES
+            .iter()
+            .any(|scheme| uri.len() > scheme.len() && uri.starts_with(scheme)),
+        DepressError::InvalidUriScheme
+    );
+    Ok(())
+}
+
+pub(crate) fn validate_mime_type(mime_type: &str) -> Result<()> {
+    require!(
+        mime_type.len() <= MIME_TYPE_LENGTH && is_mime_type(mime_type),
+        DepressError::InvalidMimeType
+    );
+    Ok(())
+}
+
+// `type/subtype`, both non-empty and without whitespace
+fn is_mime_type(mime_type: &str) -> bool {
+    match mime_type.split_once('/') {
+        Some((kind, subtype)) => {
+            !kind.is_empty()
+                && !subtype.is_empty()
+                && !mime_type.contains(char::is_whitespace)
+                && !subtype.contains('/')
+        }
+        None => false,
+    }
+}
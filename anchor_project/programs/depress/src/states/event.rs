@@ -0,0 +1,35 @@
+use crate::constants::EVENT_SCHEMA_VERSION;
+use crate::states::Config;
+use anchor_lang::prelude::*;
+
+/// Leading field of every event.
+///
+/// `sequence` is global and gapless across all events emitted by instructions given
+/// the `Config` account, so indexers can order events and detect missed ones. Events
+/// of instructions called without it are unsequenced, with `sequence` 0. `version` is
+/// `EVENT_SCHEMA_VERSION` at emission time.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct EventHeader {
+    pub version: u8,
+    pub sequence: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+impl EventHeader {
+    /// Header for the next emitted event, numbered by `config` when the instruction
+    /// was given it and unsequenced otherwise.
+    pub fn next(config: Option<&mut Config>) -> Result<EventHeader> {
+        if let Some(config) = config {
+            return config.next_header();
+        }
+
+        let clock = Clock::get()?;
+        Ok(EventHeader {
+            version: EVENT_SCHEMA_VERSION,
+            sequence: 0,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        })
+    }
+}
@@ -0,0 +1,23 @@
+use crate::states::ReactionType;
+use anchor_lang::prelude::*;
+
+/// Engagement reported to the hook program registered on a post.
+///
+/// Hook programs receive it as instruction data, after `HOOK_DISCRIMINATOR`, so an
+/// Anchor hook program implements it as `on_engagement(ctx, engagement: Engagement)`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct Engagement {
+    pub kind: EngagementKind,
+    pub post: Pubkey,
+    // The wallet engaging, also when acting through a session
+    pub actor: Pubkey,
+    // The new comment or reaction account
+    pub target: Pubkey,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub enum EngagementKind {
+    Comment,
+    PostReaction(ReactionType),
+    CommentReaction(ReactionType),
+}
@@ -0,0 +1,56 @@
+use crate::constants::{CONTENT_FLAGS, CONTENT_WARNING_LENGTH, LANGUAGE_LENGTH};
+use crate::errors::DepressError;
+use anchor_lang::prelude::*;
+
+/// Labels used by clients to filter content by language and hide sensitive content.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Default, InitSpace)]
+pub struct ContentLabels {
+    // BCP-47 language tag (e.g. "en", "pt-BR"), empty when unspecified
+    #[max_len(LANGUAGE_LENGTH)]
+    pub language: String,
+    // Shown in place of the content until the reader opts in, empty for none
+    #[max_len(CONTENT_WARNING_LENGTH)]
+    pub content_warning: String,
+    // Bitfield of `FLAG_NSFW`, `FLAG_SPOILER`, `FLAG_SPONSORED`, `FLAG_AI_GENERATED`
+    pub flags: u8,
+}
+
+impl ContentLabels {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.language.len() <= LANGUAGE_LENGTH && is_language_tag(&self.language),
+            DepressError::InvalidLanguageTag
+        );
+        require!(
+            self.content_warning.len() <= CONTENT_WARNING_LENGTH,
+            DepressError::ContentWarningTooLong
+        );
+        require!(
+            self.flags & !CONTENT_FLAGS == 0,
+            DepressError::InvalidContentFlags
+        );
+        Ok(())
+    }
+
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+// Structural BCP-47 check: a 2-3 letter primary language followed by
+// alphanumeric subtags of 1-8 characters, separated by `-`
+fn is_language_tag(tag: &str) -> bool {
+    if tag.is_empty() {
+        return true;
+    }
+
+    let mut subtags = tag.split('-');
+    let primary_ok = subtags.next().is_some_and(|primary| {
+        (2..=3).contains(&primary.len()) && primary.bytes().all(|b| b.is_ascii_alphabetic())
+    });
+
+    primary_ok
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+        })
+}
@@ -0,0 +1,138 @@
+//! Account layouts written by earlier releases, as `migrate_account` finds them.
+//!
+//! Every account is allocated at exactly `DISCRIMINATOR + INIT_SPACE`, so a layout is
+//! recognized by its discriminator and data length.
+
+/// Layouts from before accounts recorded their payer, as written by 0.1 under the
+/// Anchor discriminators of the struct names.
+pub mod payerless {
+    use crate::constants::{
+        COMMENT_LENGTH, COMMENT_VERSION, CONTENT_LENGTH, POST_VERSION, REACTION_VERSION,
+        TOPIC_LENGTH,
+    };
+    use crate::states::{self, ContentLabels, ReactionType};
+    use anchor_lang::prelude::*;
+
+    #[derive(AnchorDeserialize, InitSpace)]
+    pub struct Post {
+        pub post_author: Pubkey,
+        #[max_len(TOPIC_LENGTH)]
+        pub topic: String,
+        #[max_len(CONTENT_LENGTH)]
+        pub content: String,
+        pub likes: u64,
+        pub dislikes: u64,
+        pub comment_count: u32,
+        pub bump: u8,
+    }
+
+    /// Leaves `global_id` to `migrate_account`, which takes the next one.
+    impl From<Post> for states::Post {
+        fn from(legacy: Post) -> Self {
+            states::Post {
+                version: POST_VERSION,
+                // 0.1 had no payer, the author funded the account
+                post_author: legacy.post_author,
+                payer: legacy.post_author,
+                likes: legacy.likes,
+                dislikes: legacy.dislikes,
+                comment_count: legacy.comment_count,
+                repost_count: 0,
+                revision_count: 0,
+                chunk_count: 0,
+                total_length: legacy.content.len() as u32,
+                global_id: 0,
+                has_poll: false,
+                is_pinned: false,
+                finalized: false,
+                published: true,
+                is_draft: false,
+                removal_votes: 0,
+                bump: legacy.bump,
+                topic: legacy.topic,
+                content: legacy.content,
+                content_ref: None,
+                attachments: Vec::new(),
+                labels: ContentLabels::default(),
+                hashtags: Vec::new(),
+                mentions: Vec::new(),
+                quoted_post: None,
+                publish_at: None,
+                expires_at: None,
+                co_authors: Vec::new(),
+                hook_program: None,
+            }
+        }
+    }
+
+    #[derive(AnchorDeserialize, InitSpace)]
+    pub struct Comment {
+        pub comment_author: Pubkey,
+        pub parent_post: Pubkey,
+        #[max_len(COMMENT_LENGTH)]
+        pub content: String,
+        pub likes: u64,
+        pub dislikes: u64,
+        pub bump: u8,
+    }
+
+    impl From<Comment> for states::Comment {
+        fn from(legacy: Comment) -> Self {
+            states::Comment {
+                version: COMMENT_VERSION,
+                comment_author: legacy.comment_author,
+                payer: legacy.comment_author,
+                parent_post: legacy.parent_post,
+                likes: legacy.likes,
+                dislikes: legacy.dislikes,
+                bump: legacy.bump,
+                content: legacy.content,
+                mentions: Vec::new(),
+                attachment: None,
+                labels: ContentLabels::default(),
+            }
+        }
+    }
+
+    #[derive(AnchorDeserialize, InitSpace)]
+    pub struct ReactionPost {
+        pub reaction_author: Pubkey,
+        pub parent_post: Pubkey,
+        pub reaction: ReactionType,
+        pub bump: u8,
+    }
+
+    impl From<ReactionPost> for states::ReactionPost {
+        fn from(legacy: ReactionPost) -> Self {
+            states::ReactionPost {
+                version: REACTION_VERSION,
+                reaction_author: legacy.reaction_author,
+                payer: legacy.reaction_author,
+                parent_post: legacy.parent_post,
+                reaction: legacy.reaction,
+                bump: legacy.bump,
+            }
+        }
+    }
+
+    #[derive(AnchorDeserialize, InitSpace)]
+    pub struct ReactionComment {
+        pub reaction_author: Pubkey,
+        pub parent_comment: Pubkey,
+        pub reaction: ReactionType,
+        pub bump: u8,
+    }
+
+    impl From<ReactionComment> for states::ReactionComment {
+        fn from(legacy: ReactionComment) -> Self {
+            states::ReactionComment {
+                version: REACTION_VERSION,
+                reaction_author: legacy.reaction_author,
+                payer: legacy.reaction_author,
+                parent_comment: legacy.parent_comment,
+                reaction: legacy.reaction,
+                bump: legacy.bump,
+            }
+        }
+    }
+}
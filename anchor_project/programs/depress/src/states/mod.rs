@@ -1,7 +1,43 @@
+pub mod attachment;
+pub mod chunk;
+pub mod co_author;
 pub mod comment;
+pub mod config;
+pub mod content_ref;
+pub mod event;
+pub mod hook;
+pub mod labels;
+pub mod legacy;
+pub mod nonce;
+pub mod poll;
 pub mod post;
+pub mod post_index;
+pub mod profile;
 pub mod reaction;
+pub mod relay;
+pub mod repost;
+pub mod revision;
+pub mod session;
+pub mod tag;
 
+pub use attachment::*;
+pub use chunk::*;
+pub use co_author::*;
 pub use comment::*;
+pub use config::*;
+pub use content_ref::*;
+pub use event::*;
+pub use hook::*;
+pub use labels::*;
+pub use legacy::*;
+pub use nonce::*;
+pub use poll::*;
 pub use post::*;
+pub use post_index::*;
+pub use profile::*;
 pub use reaction::*;
+pub use relay::*;
+pub use repost::*;
+pub use revision::*;
+pub use session::*;
+pub use tag::*;
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Replay protection for messages an author signs off-chain for relayers.
+#[account]
+#[derive(InitSpace)]
+pub struct AuthorNonce {
+    // Layout version, `NONCE_VERSION` when written
+    pub version: u8,
+    pub author: Pubkey,
+    // Nonce the next relayed message must carry
+    pub nonce: u64,
+    pub bump: u8,
+}
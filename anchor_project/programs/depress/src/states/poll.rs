@@ -0,0 +1,41 @@
+use crate::constants::{MAX_POLL_OPTIONS, POLL_OPTION_LENGTH};
+use crate::states::Post;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Poll {
+    // Layout version, `POLL_VERSION` when written
+    pub version: u8,
+    pub parent_post: Pubkey,
+    #[max_len(MAX_POLL_OPTIONS, POLL_OPTION_LENGTH)]
+    pub options: Vec<String>,
+    // One tally per option, in the same order
+    #[max_len(MAX_POLL_OPTIONS)]
+    pub tallies: Vec<u64>,
+    pub closes_at: Option<i64>,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PollVote {
+    // Layout version, `POLL_VOTE_VERSION` when written
+    pub version: u8,
+    pub voter: Pubkey,
+    // Funded the account, refunded on close
+    pub payer: Pubkey,
+    pub poll: Pubkey,
+    // `global_id` of the poll's post, telling the poll apart from one re-created at
+    // the same address after the post's removal
+    pub post_global_id: u64,
+    pub option_index: u8,
+    pub bump: u8,
+}
+
+impl PollVote {
+    /// Whether `post`, the parent of the poll at `poll`, is the post voted on.
+    pub fn votes_on(&self, post: &Post) -> bool {
+        post.global_id == self.post_global_id
+    }
+}
@@ -1,16 +1,105 @@
-use crate::constants::{CONTENT_LENGTH, TOPIC_LENGTH};
+use crate::constants::{
+    CONTENT_LENGTH, HASHTAG_LENGTH, MAX_CO_AUTHORS, MAX_HASHTAGS, MAX_MENTIONS,
+    MAX_POST_ATTACHMENTS, POST_DISCRIMINATOR, TOPIC_LENGTH,
+};
+use crate::states::{Attachment, CoAuthor, ContentLabels, ContentRef};
 use anchor_lang::prelude::*;
 
-#[account]
+/// Fixed-size fields come first, at the `POST_*_OFFSET` offsets, so clients can
+/// match them with `memcmp` filters; variable-size fields follow.
+#[account(discriminator = POST_DISCRIMINATOR)]
 #[derive(InitSpace)]
 pub struct Post {
+    // Layout version, `POST_VERSION` when written
+    pub version: u8,
     pub post_author: Pubkey,
-    #[max_len(TOPIC_LENGTH)]
-    pub topic: String,
-    #[max_len(CONTENT_LENGTH)]
-    pub content: String,
+    // Funded the account, and with it any poll and chunks; refunded on close
+    pub payer: Pubkey,
     pub likes: u64,
     pub dislikes: u64,
     pub comment_count: u32,
+    pub repost_count: u32,
+    pub revision_count: u32,
+    pub chunk_count: u32,
+    // Inline content plus all chunks, in bytes
+    pub total_length: u32,
+    // Position in the global post sequence, see `PostIndex`
+    pub global_id: u64,
+    pub has_poll: bool,
+    pub is_pinned: bool,
+    pub finalized: bool,
+    pub published: bool,
+    // Drafts are private to their author until `draft_publish` runs
+    pub is_draft: bool,
+    // Removal votes, bit 0 for `post_author` and bit `i + 1` for co-author `i`
+    pub removal_votes: u8,
     pub bump: u8,
+    #[max_len(TOPIC_LENGTH)]
+    pub topic: String,
+    #[max_len(CONTENT_LENGTH)]
+    pub content: String,
+    // Set for posts whose content lives off-chain, `content` is then empty
+    pub content_ref: Option<ContentRef>,
+    #[max_len(MAX_POST_ATTACHMENTS)]
+    pub attachments: Vec<Attachment>,
+    pub labels: ContentLabels,
+    #[max_len(MAX_HASHTAGS, HASHTAG_LENGTH)]
+    pub hashtags: Vec<String>,
+    #[max_len(MAX_MENTIONS)]
+    pub mentions: Vec<Pubkey>,
+    // Set when this post quotes another post
+    pub quoted_post: Option<Pubkey>,
+    // Scheduled publishing time, engagement is rejected before it
+    pub publish_at: Option<i64>,
+    // Set for ephemeral posts, which turn read-only and closable once it passes
+    pub expires_at: Option<i64>,
+    #[max_len(MAX_CO_AUTHORS)]
+    pub co_authors: Vec<CoAuthor>,
+    // Invoked on every comment and reaction, and able to reject them
+    pub hook_program: Option<Pubkey>,
+}
+
+impl Post {
+    /// Whether the post is live at `now`, i.e. it is not a draft, all co-authors
+    /// approved it and its publishing time has passed.
+    pub fn is_published_at(&self, now: i64) -> bool {
+        if self.is_draft || !self.is_approved() {
+            return false;
+        }
+        match self.publish_at {
+            Some(publish_at) => now >= publish_at,
+            None => true,
+        }
+    }
+
+    /// Whether the post has expired at `now`. Posts without `expires_at` never expire.
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        match self.expires_at {
+            Some(expires_at) => now >= expires_at,
+            None => false,
+        }
+    }
+
+    /// Whether every co-author approved the post.
+    pub fn is_approved(&self) -> bool {
+        self.co_authors.iter().all(|co_author| co_author.approved)
+    }
+
+    /// Position of `author` among the post's authors: 0 for `post_author`, `i + 1`
+    /// for co-author `i`.
+    pub fn author_index(&self, author: &Pubkey) -> Option<usize> {
+        if *author == self.post_author {
+            return Some(0);
+        }
+        self.co_authors
+            .iter()
+            .position(|co_author| co_author.author == *author)
+            .map(|i| i + 1)
+    }
+
+    /// Whether a strict majority of the authors voted for removal in `votes`.
+    pub fn is_removal_approved(&self, votes: u8) -> bool {
+        let authors = self.co_authors.len() as u32 + 1;
+        votes.count_ones() * 2 > authors
+    }
 }
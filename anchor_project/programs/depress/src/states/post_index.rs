@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Entry of the global post sequence, a PDA with seeds: [INDEX_SEED, global_id].
+///
+/// Ids follow creation order, so clients page backwards through the feed from
+/// `Config::post_count - 1` without scanning. Entries of removed posts are closed
+/// with them, leaving gaps in the sequence.
+#[account]
+#[derive(InitSpace)]
+pub struct PostIndex {
+    // Layout version, `INDEX_VERSION` when written
+    pub version: u8,
+    pub post: Pubkey,
+    pub bump: u8,
+}
@@ -0,0 +1,27 @@
+use crate::constants::MAX_PINNED_POSTS;
+use crate::errors::DepressError;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    // Layout version, `PROFILE_VERSION` when written
+    pub version: u8,
+    pub author: Pubkey,
+    #[max_len(MAX_PINNED_POSTS)]
+    pub pinned_posts: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl Profile {
+    /// Removes `post` from the pinned posts, keeping the order of the others.
+    pub fn unpin(&mut self, post: &Pubkey) -> Result<()> {
+        let index = self
+            .pinned_posts
+            .iter()
+            .position(|pinned| pinned == post)
+            .ok_or(DepressError::PostNotPinned)?;
+        self.pinned_posts.remove(index);
+        Ok(())
+    }
+}
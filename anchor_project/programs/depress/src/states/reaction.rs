@@ -6,10 +6,27 @@ pub enum ReactionType {
     Dislike,
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub enum ReactionTarget {
+    Post,
+    Comment,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct BatchReaction {
+    pub target: ReactionTarget,
+    // The reaction to add, `None` removes the existing one
+    pub reaction: Option<ReactionType>,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct ReactionPost {
+    // Layout version, `REACTION_VERSION` when written
+    pub version: u8,
     pub reaction_author: Pubkey,
+    // Funded the account, refunded on close
+    pub payer: Pubkey,
     pub parent_post: Pubkey,
     pub reaction: ReactionType,
     pub bump: u8,
@@ -18,7 +35,11 @@ pub struct ReactionPost {
 #[account]
 #[derive(InitSpace)]
 pub struct ReactionComment {
+    // Layout version, `REACTION_VERSION` when written
+    pub version: u8,
     pub reaction_author: Pubkey,
+    // Funded the account, refunded on close
+    pub payer: Pubkey,
     pub parent_comment: Pubkey,
     pub reaction: ReactionType,
     pub bump: u8,
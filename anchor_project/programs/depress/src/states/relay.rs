@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// Message an author signs off-chain so that any relayer can submit it.
+///
+/// The signed bytes are the Borsh serialization of the message, which binds it to
+/// this program and to the author's current nonce. A comment also names its parent
+/// post's `global_id`, so it cannot land on a post later created at the same address.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub enum RelayedMessage {
+    Post {
+        program_id: Pubkey,
+        nonce: u64,
+        topic: String,
+        content: String,
+    },
+    Comment {
+        program_id: Pubkey,
+        nonce: u64,
+        parent_post: Pubkey,
+        parent_global_id: u64,
+        content: String,
+    },
+}
+
+impl RelayedMessage {
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.serialize(&mut bytes)?;
+        Ok(bytes)
+    }
+}
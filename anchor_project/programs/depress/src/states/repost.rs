@@ -0,0 +1,24 @@
+use crate::states::Post;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Repost {
+    // Layout version, `REPOST_VERSION` when written
+    pub version: u8,
+    pub reposter: Pubkey,
+    // Funded the account, refunded on close
+    pub payer: Pubkey,
+    pub original_post: Pubkey,
+    // `global_id` of the original post, telling it apart from a post re-created at
+    // the same address after its removal
+    pub original_global_id: u64,
+    pub bump: u8,
+}
+
+impl Repost {
+    /// Whether `post`, at `original_post`, is the post that was reposted.
+    pub fn reposts(&self, post: &Post) -> bool {
+        post.global_id == self.original_global_id
+    }
+}
@@ -0,0 +1,22 @@
+use crate::constants::CONTENT_LENGTH;
+use crate::states::ContentRef;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PostRevision {
+    // Layout version, `REVISION_VERSION` when written
+    pub version: u8,
+    pub post: Pubkey,
+    pub post_author: Pubkey,
+    // Funded the account, refunded on close
+    pub payer: Pubkey,
+    pub revision_number: u32,
+    // Content of the post before this edit
+    #[max_len(CONTENT_LENGTH)]
+    pub previous_content: String,
+    // Content reference of the post before this edit, for off-chain posts
+    pub previous_content_ref: Option<ContentRef>,
+    pub edited_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,65 @@
+use crate::errors::DepressError;
+use anchor_lang::prelude::*;
+
+/// Delegation from a wallet (`authority`) to an ephemeral `session_key`, which may
+/// sign the `permissions` actions on the wallet's behalf until `expires_at`.
+#[account]
+#[derive(InitSpace)]
+pub struct Session {
+    // Layout version, `SESSION_VERSION` when written
+    pub version: u8,
+    pub authority: Pubkey,
+    pub session_key: Pubkey,
+    // Funded the account, refunded on close
+    pub payer: Pubkey,
+    pub expires_at: i64,
+    // Bitmask of `SESSION_REACT`, `SESSION_COMMENT` and `SESSION_POST`
+    pub permissions: u8,
+    pub bump: u8,
+}
+
+impl Session {
+    /// The wallet acting through `signer`: the session authority when a session is
+    /// given, `signer` itself otherwise.
+    pub fn author_of(signer: Pubkey, session: Option<&Session>) -> Pubkey {
+        match session {
+            Some(session) => session.authority,
+            None => signer,
+        }
+    }
+
+    /// The account recorded as the payer of rent funded by `payer`: the session
+    /// authority when the session key itself pays, as the key is discarded once
+    /// the session ends, `payer` otherwise.
+    pub fn rent_payer(payer: Pubkey, session: Option<&Session>) -> Pubkey {
+        match session {
+            Some(session) if session.session_key == payer => session.authority,
+            _ => payer,
+        }
+    }
+
+    /// `author_of` as a PDA seed. Being a call, the seed is left out of the IDL,
+    /// which cannot describe it, and clients derive these PDAs themselves.
+    pub fn author_seed(signer: Pubkey, session: Option<&Session>) -> [u8; 32] {
+        Session::author_of(signer, session).to_bytes()
+    }
+
+    /// Checks that `signer` may perform `action` at `now`, through `session` when
+    /// given, and returns the wallet it acts for.
+    pub fn authorize(
+        signer: Pubkey,
+        session: Option<&Session>,
+        action: u8,
+        now: i64,
+    ) -> Result<Pubkey> {
+        if let Some(session) = session {
+            require_keys_eq!(session.session_key, signer, DepressError::InvalidSessionKey);
+            require!(now < session.expires_at, DepressError::SessionExpired);
+            require!(
+                session.permissions & action == action,
+                DepressError::SessionNotPermitted
+            );
+        }
+        Ok(Session::author_of(signer, session))
+    }
+}
@@ -0,0 +1,59 @@
+use crate::constants::{HASHTAG_LENGTH, MAX_HASHTAGS};
+use crate::errors::DepressError;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Tag {
+    // Layout version, `TAG_VERSION` when written
+    pub version: u8,
+    #[max_len(HASHTAG_LENGTH)]
+    pub tag: String,
+    pub usage_count: u64,
+    pub last_post: Pubkey,
+    pub bump: u8,
+}
+
+impl Tag {
+    /// Extracts the distinct `#hashtags` from `content`, lowercased and in order of
+    /// first appearance. A hashtag starts at a `#` that is not preceded by a word
+    /// character and runs over ASCII letters, digits and `_`.
+    pub fn parse_hashtags(content: &str) -> Result<Vec<String>> {
+        let bytes = content.as_bytes();
+        let mut hashtags: Vec<String> = Vec::new();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let starts_tag = bytes[i] == b'#' && (i == 0 || !is_tag_byte(bytes[i - 1]));
+            if !starts_tag {
+                i += 1;
+                continue;
+            }
+
+            let start = i + 1;
+            let mut end = start;
+            while end < bytes.len() && is_tag_byte(bytes[end]) {
+                end += 1;
+            }
+            i = end;
+
+            // A lone `#` is not a hashtag
+            if end == start {
+                continue;
+            }
+            require!(end - start <= HASHTAG_LENGTH, DepressError::HashtagTooLong);
+
+            let tag = content[start..end].to_ascii_lowercase();
+            if !hashtags.contains(&tag) {
+                require!(hashtags.len() < MAX_HASHTAGS, DepressError::TooManyHashtags);
+                hashtags.push(tag);
+            }
+        }
+
+        Ok(hashtags)
+    }
+}
+
+fn is_tag_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
@@ -0,0 +1,396 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program::{allocate, assign, create_account, transfer};
+use anchor_lang::system_program::{Allocate, Assign, CreateAccount, Transfer};
+
+use crate::constants::{
+    DISCRIMINATOR, HOOK_DISCRIMINATOR, MAX_CO_AUTHORS, MAX_MENTIONS, SHARE_BASIS_POINTS, TAG_SEED,
+    TAG_VERSION,
+};
+use crate::errors::DepressError;
+use crate::states::{CoAuthor, Config, Engagement, EventHeader, Post, Tag};
+
+/// Creates a program-owned PDA account of `space` bytes funded by `payer`.
+///
+/// Mirrors what Anchor's `init` constraint does, for accounts that are only known
+/// at runtime (e.g. passed through `remaining_accounts`). Accounts that already hold
+/// lamports are topped up, allocated and assigned instead of created.
+pub fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer = &[signer_seeds];
+
+    if account.lamports() == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(account.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer,
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            signer,
+        ),
+        &crate::ID,
+    )
+}
+
+/// Resizes a program-owned `account` to `space` bytes, with `payer` topping up its
+/// lamports to the rent-exempt minimum of the new size. Any excess stays on the account.
+pub fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let top_up = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.resize(space)?;
+    Ok(())
+}
+
+/// Records `post` in the `Tag` index account of every hashtag, creating missing
+/// tag accounts on the way.
+///
+/// `tag_accounts` must hold the `[TAG_SEED, tag]` PDAs in the same order as `hashtags`.
+pub fn index_hashtags<'info>(
+    hashtags: &[String],
+    tag_accounts: &'info [AccountInfo<'info>],
+    post: Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require!(
+        tag_accounts.len() >= hashtags.len(),
+        DepressError::MissingTagAccount
+    );
+
+    for (hashtag, tag_info) in hashtags.iter().zip(tag_accounts) {
+        let (tag_key, bump) =
+            Pubkey::find_program_address(&[TAG_SEED.as_bytes(), hashtag.as_bytes()], &crate::ID);
+        require_keys_eq!(tag_info.key(), tag_key, DepressError::InvalidTagAccount);
+
+        if tag_info.owner == &crate::ID {
+            // Existing tag, bump its usage
+            let mut tag = Account::<Tag>::try_from(tag_info)?;
+            tag.usage_count += 1;
+            tag.last_post = post;
+            tag.exit(&crate::ID)?;
+        } else {
+            create_pda_account(
+                tag_info,
+                payer,
+                system_program,
+                DISCRIMINATOR + Tag::INIT_SPACE,
+                &[TAG_SEED.as_bytes(), hashtag.as_bytes(), &[bump]],
+            )?;
+
+            let tag = Tag {
+                version: TAG_VERSION,
+                tag: hashtag.clone(),
+                usage_count: 1,
+                last_post: post,
+                bump,
+            };
+            tag.try_serialize(&mut &mut tag_info.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Drops a usage from the `Tag` index account of every hashtag of a post being
+/// removed. Tag accounts stay open, even once unused.
+///
+/// `tag_accounts` must hold the `[TAG_SEED, tag]` PDAs in the same order as `hashtags`.
+pub fn unindex_hashtags(hashtags: &[String], tag_accounts: &[AccountInfo]) -> Result<()> {
+    require!(
+        tag_accounts.len() >= hashtags.len(),
+        DepressError::MissingTagAccount
+    );
+
+    for (hashtag, tag_info) in hashtags.iter().zip(tag_accounts) {
+        let (tag_key, _) =
+            Pubkey::find_program_address(&[TAG_SEED.as_bytes(), hashtag.as_bytes()], &crate::ID);
+        require_keys_eq!(tag_info.key(), tag_key, DepressError::InvalidTagAccount);
+        require_keys_eq!(*tag_info.owner, crate::ID, DepressError::InvalidTagAccount);
+
+        let mut tag = Tag::try_deserialize(&mut &tag_info.try_borrow_data()?[..])?;
+        tag.usage_count = tag.usage_count.saturating_sub(1);
+        tag.try_serialize(&mut &mut tag_info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
+
+/// Checks that `mentions` is bounded, free of duplicates and does not contain `author`.
+pub fn validate_mentions(author: &Pubkey, mentions: &[Pubkey]) -> Result<()> {
+    require!(
+        mentions.len() <= MAX_MENTIONS,
+        DepressError::TooManyMentions
+    );
+
+    for (i, mentioned) in mentions.iter().enumerate() {
+        require!(mentioned != author, DepressError::SelfMention);
+        require!(
+            !mentions[..i].contains(mentioned),
+            DepressError::DuplicateMention
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that `co_authors` is bounded, free of duplicates, does not contain `author`
+/// and leaves a non-negative share to `author`.
+pub fn validate_co_authors(author: &Pubkey, co_authors: &[CoAuthor]) -> Result<()> {
+    require!(
+        co_authors.len() <= MAX_CO_AUTHORS,
+        DepressError::TooManyCoAuthors
+    );
+
+    let mut total_share: u32 = 0;
+    for (i, co_author) in co_authors.iter().enumerate() {
+        require!(
+            co_author.author != *author
+                && !co_authors[..i]
+                    .iter()
+                    .any(|other| other.author == co_author.author),
+            DepressError::DuplicateCoAuthor
+        );
+        total_share += co_author.share_bps as u32;
+    }
+    require!(
+        total_share <= SHARE_BASIS_POINTS as u32,
+        DepressError::InvalidCoAuthorShares
+    );
+
+    Ok(())
+}
+
+/// Numbers events from the `Config` account, if the instruction was given it, and
+/// emits them through a self-CPI signed by the event authority PDA, like `emit_cpi!`
+/// does.
+///
+/// Used by helpers that have no `ctx` in scope for the macro.
+pub struct EventEmitter<'a, 'info> {
+    config: Option<&'a mut Config>,
+    event_authority: AccountInfo<'info>,
+    event_authority_bump: u8,
+}
+
+impl<'a, 'info> EventEmitter<'a, 'info> {
+    pub fn new(
+        config: Option<&'a mut Config>,
+        event_authority: AccountInfo<'info>,
+        event_authority_bump: u8,
+    ) -> Self {
+        Self {
+            config,
+            event_authority,
+            event_authority_bump,
+        }
+    }
+
+    /// Emits the event built from the next header as the data of an inner
+    /// instruction to this program.
+    pub fn emit<E: anchor_lang::Event>(
+        &mut self,
+        event: impl FnOnce(EventHeader) -> E,
+    ) -> Result<()> {
+        let event = event(EventHeader::next(self.config.as_deref_mut())?);
+        let data = [EVENT_IX_TAG_LE, &event.data()].concat();
+        let ix = Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![AccountMeta::new_readonly(self.event_authority.key(), true)],
+        );
+        invoke_signed(
+            &ix,
+            std::slice::from_ref(&self.event_authority),
+            &[&[b"__event_authority", &[self.event_authority_bump]]],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Closes a program-owned account, sending its lamports to `destination`.
+pub fn close_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? += lamports;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false).map_err(Into::into)
+}
+
+/// Checks that the instruction right before the current one makes the Ed25519
+/// program verify a single signature by `signer` over `message`.
+///
+/// The signature, public key and message must all be stored in that
+/// instruction's own data, as produced by the standard Ed25519 instruction builders.
+pub fn verify_ed25519_instruction(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = solana_instructions_sysvar::load_current_index_checked(instructions)?;
+    require!(current_index > 0, DepressError::MissingSignatureInstruction);
+    let instruction = solana_instructions_sysvar::load_instruction_at_checked(
+        current_index as usize - 1,
+        instructions,
+    )?;
+    require_keys_eq!(
+        instruction.program_id,
+        ed25519_program::ID,
+        DepressError::MissingSignatureInstruction
+    );
+
+    // Header: signature count and padding, then one offsets entry of seven u16
+    let data = &instruction.data;
+    let read_u16 = |at: usize| -> Result<u16> {
+        data.get(at..at + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+            .ok_or_else(|| DepressError::InvalidSignatureInstruction.into())
+    };
+    require!(
+        data.first() == Some(&1),
+        DepressError::InvalidSignatureInstruction
+    );
+    let public_key_offset = read_u16(6)? as usize;
+    let message_offset = read_u16(10)? as usize;
+    let message_size = read_u16(12)? as usize;
+    for index_at in [4, 8, 14] {
+        require!(
+            read_u16(index_at)? == u16::MAX,
+            DepressError::InvalidSignatureInstruction
+        );
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(DepressError::InvalidSignatureInstruction)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(DepressError::InvalidSignatureInstruction)?;
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        DepressError::InvalidSignatureInstruction
+    );
+
+    Ok(())
+}
+
+/// Invokes the hook program registered on `post`, if any, with `engagement`.
+///
+/// The hook receives the post, read-only, followed by `extra_accounts` stripped
+/// of their signer privilege. Writable DePress accounts, such as the post or the
+/// `Config`, are rejected among them: the instruction writes its own copy of them
+/// back after the hook. Hooks run before the new account and the post counters
+/// are written back, so they only see the state from before the action. Any error
+/// returned by the hook rejects the action.
+pub fn invoke_post_hook<'info>(
+    post: &Account<'info, Post>,
+    hook_program: Option<&AccountInfo<'info>>,
+    extra_accounts: &[AccountInfo<'info>],
+    engagement: &Engagement,
+) -> Result<()> {
+    let Some(hook_key) = post.hook_program else {
+        return Ok(());
+    };
+    let hook_program = hook_program.ok_or(DepressError::MissingHookProgram)?;
+    require_keys_eq!(
+        hook_program.key(),
+        hook_key,
+        DepressError::InvalidHookProgram
+    );
+
+    for account in extra_accounts {
+        require!(
+            !(account.is_writable && *account.owner == crate::ID),
+            DepressError::HookAccountNotAllowed
+        );
+    }
+
+    let mut data = HOOK_DISCRIMINATOR.to_vec();
+    engagement.serialize(&mut data)?;
+    let mut accounts = vec![AccountMeta::new_readonly(post.key(), false)];
+    accounts.extend(extra_accounts.iter().map(|account| AccountMeta {
+        pubkey: account.key(),
+        is_signer: false,
+        is_writable: account.is_writable,
+    }));
+
+    let mut account_infos = vec![post.to_account_info(), hook_program.clone()];
+    account_infos.extend_from_slice(extra_accounts);
+    invoke(
+        &Instruction {
+            program_id: hook_key,
+            accounts,
+            data,
+        },
+        &account_infos,
+    )
+    .map_err(Into::into)
+}
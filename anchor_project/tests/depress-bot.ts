@@ -0,0 +1,220 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import crypto from "crypto";
+import { Depress } from "../target/types/depress";
+import { DepressBot } from "../target/types/depress_bot";
+
+const POST_SEED = "POST_SEED";
+const POST_REACTION = "POST_REACTION_SEED";
+const COMMENT_SEED = "COMMENT_SEED";
+const TAG_SEED = "TAG_SEED";
+const BOT_AUTHOR_SEED = "BOT_AUTHOR_SEED";
+const CONFIG_SEED = "CONFIG_SEED";
+const INDEX_SEED = "INDEX_SEED";
+const EVENT_AUTHORITY_SEED = "__event_authority";
+
+describe("DePress Bot", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.depress as Program<Depress>;
+  const bot = anchor.workspace.depressBot as Program<DepressBot>;
+
+  const admin = provider.wallet;
+  const [bot_author] = PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(BOT_AUTHOR_SEED),
+      admin.publicKey.toBuffer(),
+    ],
+    bot.programId
+  );
+
+  const [config_pkey] = PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode(CONFIG_SEED)],
+    program.programId
+  );
+  const [event_authority] = PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode(EVENT_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  before(async () => {
+    // The config numbering all events is created once per cluster
+    if (!(await provider.connection.getAccountInfo(config_pkey))) {
+      await program.methods
+        .configInitialize()
+        .accounts({
+          config: config_pkey,
+          payer: admin.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc({ commitment: "confirmed" });
+    }
+  });
+
+  const topic_bot = "Daily digest";
+  const content_bot = "Automated summary of the day #digest";
+  const comment_bot = "Posted by a program";
+
+  it("Should post as a PDA author through CPI", async () => {
+    const [post_pkey] = getPostAddress(
+      topic_bot,
+      bot_author,
+      program.programId
+    );
+    const [tag_pkey] = getTagAddress("digest", program.programId);
+    const { postCount } = await program.account.config.fetch(config_pkey);
+    const [index_pkey] = getIndexAddress(postCount, program.programId);
+    await bot.methods
+      .botPost(topic_bot, content_bot)
+      .accounts({
+        admin: admin.publicKey,
+        botAuthor: bot_author,
+        post: post_pkey,
+        postIndex: index_pkey,
+        config: config_pkey,
+        eventAuthority: event_authority,
+        depressProgram: program.programId,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts([
+        { pubkey: tag_pkey, isSigner: false, isWritable: true },
+      ])
+      .rpc({ commitment: "confirmed" });
+
+    const postData = await program.account.post.fetch(post_pkey);
+    assert.strictEqual(postData.postAuthor.toString(), bot_author.toString());
+    assert.strictEqual(postData.globalId.toString(), postCount.toString());
+    assert.strictEqual(
+      postData.payer.toString(),
+      admin.publicKey.toString()
+    );
+    assert.strictEqual(postData.content, content_bot);
+  });
+
+  it("Should comment as a PDA author through CPI", async () => {
+    const [post_pkey] = getPostAddress(
+      topic_bot,
+      bot_author,
+      program.programId
+    );
+    const [comment_pkey] = getCommentAddress(
+      comment_bot,
+      bot_author,
+      post_pkey,
+      program.programId
+    );
+    await bot.methods
+      .botComment(comment_bot)
+      .accounts({
+        admin: admin.publicKey,
+        botAuthor: bot_author,
+        post: post_pkey,
+        comment: comment_pkey,
+        config: config_pkey,
+        eventAuthority: event_authority,
+        depressProgram: program.programId,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const commentData = await program.account.comment.fetch(comment_pkey);
+    assert.strictEqual(
+      commentData.commentAuthor.toString(),
+      bot_author.toString()
+    );
+    const postData = await program.account.post.fetch(post_pkey);
+    assert.strictEqual(postData.commentCount.toString(), "1");
+  });
+
+  it("Should like a post as a PDA author through CPI", async () => {
+    const [post_pkey] = getPostAddress(
+      topic_bot,
+      bot_author,
+      program.programId
+    );
+    const [reaction_pkey] = PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode(POST_REACTION),
+        bot_author.toBuffer(),
+        post_pkey.toBuffer(),
+      ],
+      program.programId
+    );
+    await bot.methods
+      .botLike()
+      .accounts({
+        admin: admin.publicKey,
+        botAuthor: bot_author,
+        post: post_pkey,
+        postReaction: reaction_pkey,
+        config: config_pkey,
+        eventAuthority: event_authority,
+        depressProgram: program.programId,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const postData = await program.account.post.fetch(post_pkey);
+    assert.strictEqual(postData.likes.toString(), "1");
+  });
+});
+
+function getPostAddress(
+  topic: string,
+  author: PublicKey,
+  programID: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(POST_SEED),
+      anchor.utils.bytes.utf8.encode(topic),
+      author.toBuffer(),
+    ],
+    programID
+  );
+}
+
+function getCommentAddress(
+  comment_content: string,
+  author: PublicKey,
+  parent_post: PublicKey,
+  programID: PublicKey
+) {
+  const content_seed = crypto
+    .createHash("sha256")
+    .update(comment_content, "utf-8")
+    .digest();
+
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(COMMENT_SEED),
+      author.toBuffer(),
+      content_seed,
+      parent_post.toBuffer(),
+    ],
+    programID
+  );
+}
+
+function getTagAddress(tag: string, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(TAG_SEED),
+      anchor.utils.bytes.utf8.encode(tag),
+    ],
+    programID
+  );
+}
+
+function getIndexAddress(globalId: anchor.BN, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(INDEX_SEED),
+      globalId.toArrayLike(Buffer, "le", 8),
+    ],
+    programID
+  );
+}
@@ -9,6 +9,32 @@ const POST_SEED = "POST_SEED";
 const POST_REACTION = "POST_REACTION_SEED";
 const COMMENT_SEED = "COMMENT_SEED";
 const COMMENT_REACTION = "COMMENT_REACTION_SEED";
+const TAG_SEED = "TAG_SEED";
+const REPOST_SEED = "REPOST_SEED";
+const POLL_SEED = "POLL_SEED";
+const POLL_VOTE_SEED = "POLL_VOTE_SEED";
+const PROFILE_SEED = "PROFILE_SEED";
+const REVISION_SEED = "REVISION_SEED";
+const CHUNK_SEED = "CHUNK_SEED";
+const SESSION_SEED = "SESSION_SEED";
+const NONCE_SEED = "NONCE_SEED";
+const CONFIG_SEED = "CONFIG_SEED";
+const INDEX_SEED = "INDEX_SEED";
+
+// Fixed-size field offsets, discriminator included
+const POST_AUTHOR_OFFSET = 9;
+const POST_LIKES_OFFSET = 73;
+const POST_PUBLISHED_OFFSET = 120;
+const POST_IS_DRAFT_OFFSET = 121;
+const REACTION_PARENT_OFFSET = 73;
+// Discriminator plus `INIT_SPACE` of the current account layouts
+const ACCOUNT_SPACE = {
+  Post: 3310,
+  Comment: 896,
+  ReactionPost: 107,
+  ReactionComment: 107,
+  PostIndex: 42,
+};
 
 describe("DePress", () => {
   const provider = anchor.AnchorProvider.env();
@@ -49,6 +75,26 @@ describe("DePress", () => {
   const unicode_topic = "🚀 Crypto";
   const unicode_content = "Testing with emojis 🎉✨🔥";
 
+  const no_labels = { language: "", contentWarning: "", flags: 0 };
+  const FLAG_NSFW = 1 << 0;
+  const FLAG_SPOILER = 1 << 1;
+  const FLAG_AI_GENERATED = 1 << 3;
+
+  before(async () => {
+    // The config numbering all events is created once per cluster
+    const [config_pkey] = getConfigAddress(program.programId);
+    if (!(await provider.connection.getAccountInfo(config_pkey))) {
+      await program.methods
+        .configInitialize()
+        .accounts({
+          config: config_pkey,
+          payer: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc({ commitment: "confirmed" });
+    }
+  });
+
   describe("Add Post", async () => {
     it("Should successfully create a post with valid topic and content", async () => {
       await airdrop(provider.connection, bob.publicKey);
@@ -59,11 +105,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd(topic_bob1, content_bob1)
+        .postAdd(
+          topic_bob1,
+          content_bob1,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -88,11 +146,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd(topic_edge_case, content_bob1)
+        .postAdd(
+          topic_edge_case,
+          content_bob1,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -117,11 +187,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd("EdgeContent", content_edge_case)
+        .postAdd(
+          "EdgeContent",
+          content_edge_case,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -146,11 +228,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd("EmptyContent", empty_content)
+        .postAdd(
+          "EmptyContent",
+          empty_content,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -175,11 +269,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd(single_char_topic, single_char_content)
+        .postAdd(
+          single_char_topic,
+          single_char_content,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -204,11 +310,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd(unicode_topic, unicode_content)
+        .postAdd(
+          unicode_topic,
+          unicode_content,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -235,11 +353,23 @@ describe("DePress", () => {
         );
 
         await program.methods
-          .postAdd(topic_bob2, content_bob2)
+          .postAdd(
+            topic_bob2,
+            content_bob2,
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
           .accounts({
             postAuthor: bob.publicKey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
           })
           .signers([bob])
           .rpc({ commitment: "confirmed" });
@@ -268,11 +398,23 @@ describe("DePress", () => {
         );
 
         await program.methods
-          .postAdd(topic_bob3, content_bob3)
+          .postAdd(
+            topic_bob3,
+            content_bob3,
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
           .accounts({
             postAuthor: bob.publicKey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
           })
           .signers([bob])
           .rpc({ commitment: "confirmed" });
@@ -302,11 +444,23 @@ describe("DePress", () => {
         );
 
         await program.methods
-          .postAdd(topic_bob1, "Different content")
+          .postAdd(
+            topic_bob1,
+            "Different content",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
           .accounts({
             postAuthor: bob.publicKey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
           })
           .signers([bob])
           .rpc({ commitment: "confirmed" });
@@ -332,11 +486,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd(topic_bob4, content_bob4)
+        .postAdd(
+          topic_bob4,
+          content_bob4,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -361,11 +527,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd(topic_bob1, "Charlie's version")
+        .postAdd(
+          topic_bob1,
+          "Charlie's version",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: charlie.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
         })
         .signers([charlie])
         .rpc({ commitment: "confirmed" });
@@ -393,11 +571,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd(removableTopic, "This post will be removed")
+        .postAdd(
+          removableTopic,
+          "This post will be removed",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -419,6 +609,7 @@ describe("DePress", () => {
           post: post_pkey,
           postAuthor: bob.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -455,6 +646,7 @@ describe("DePress", () => {
             post: post_pkey,
             postAuthor: bob.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
           })
           .signers([bob])
           .rpc({ commitment: "confirmed" });
@@ -482,11 +674,23 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .postAdd(unauthorizedTopic, "Only Bob can delete this")
+        .postAdd(
+          unauthorizedTopic,
+          "Only Bob can delete this",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
         .accounts({
           postAuthor: bob.publicKey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -499,6 +703,7 @@ describe("DePress", () => {
             post: post_pkey,
             postAuthor: alice.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -540,6 +745,7 @@ describe("DePress", () => {
           postReaction: reaction_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -604,6 +810,7 @@ describe("DePress", () => {
             postReaction: reaction_pkey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -652,6 +859,7 @@ describe("DePress", () => {
             postReaction: reaction_pkey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -699,6 +907,7 @@ describe("DePress", () => {
           postReaction: reaction_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -763,6 +972,7 @@ describe("DePress", () => {
             postReaction: reaction_pkey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -798,6 +1008,7 @@ describe("DePress", () => {
             postReaction: reaction_pkey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -844,6 +1055,7 @@ describe("DePress", () => {
           postReaction: reaction_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
         })
         .signers([charlie])
         .rpc({ commitment: "confirmed" });
@@ -889,6 +1101,7 @@ describe("DePress", () => {
             postReaction: reaction_pkey,
             post: fake_post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -928,6 +1141,7 @@ describe("DePress", () => {
           postReaction: reaction_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -992,6 +1206,7 @@ describe("DePress", () => {
           postReaction: reaction_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1035,6 +1250,7 @@ describe("DePress", () => {
             postReaction: reaction_pkey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
           })
           .signers([bob])
           .rpc({ commitment: "confirmed" });
@@ -1074,6 +1290,7 @@ describe("DePress", () => {
             postReaction: reaction_pkey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -1111,12 +1328,13 @@ describe("DePress", () => {
       let should_fail = "This Should Fail";
       try {
         await program.methods
-          .commentAdd(comment_alice1)
+          .commentAdd(comment_alice1, [], null, no_labels)
           .accounts({
             commentAuthor: alice.publicKey,
             comment: comment_pkey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -1150,12 +1368,13 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .commentAdd(comment_alice2)
+        .commentAdd(comment_alice2, [], null, no_labels)
         .accounts({
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1184,12 +1403,13 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .commentAdd(max_comment)
+        .commentAdd(max_comment, [], null, no_labels)
         .accounts({
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1219,12 +1439,13 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .commentAdd(empty_comment)
+        .commentAdd(empty_comment, [], null, no_labels)
         .accounts({
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1254,12 +1475,13 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .commentAdd(unicode_comment)
+        .commentAdd(unicode_comment, [], null, no_labels)
         .accounts({
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1289,12 +1511,13 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .commentAdd(charlie_comment)
+        .commentAdd(charlie_comment, [], null, no_labels)
         .accounts({
           commentAuthor: charlie.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
         })
         .signers([charlie])
         .rpc({ commitment: "confirmed" });
@@ -1326,12 +1549,13 @@ describe("DePress", () => {
       let should_fail = "This should fail";
       try {
         await program.methods
-          .commentAdd(test_comment)
+          .commentAdd(test_comment, [], null, no_labels)
           .accounts({
             commentAuthor: alice.publicKey,
             comment: comment_pkey,
             post: fake_post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -1366,12 +1590,13 @@ describe("DePress", () => {
       let should_fail = "This should fail";
       try {
         await program.methods
-          .commentAdd(comment_alice2)
+          .commentAdd(comment_alice2, [], null, no_labels)
           .accounts({
             commentAuthor: alice.publicKey,
             comment: comment_pkey,
             post: post_pkey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -1409,6 +1634,7 @@ describe("DePress", () => {
         .accounts({
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1451,6 +1677,7 @@ describe("DePress", () => {
           .accounts({
             commentAuthor: alice.publicKey,
             comment: comment_pkey,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -1490,6 +1717,7 @@ describe("DePress", () => {
           .accounts({
             commentAuthor: charlie.publicKey, // Charlie trying to remove Alice's comment
             comment: comment_pkey,
+            payer: charlie.publicKey,
           })
           .signers([charlie])
           .rpc({ commitment: "confirmed" });
@@ -1523,12 +1751,13 @@ describe("DePress", () => {
 
       // Recreate the same comment that was deleted
       await program.methods
-        .commentAdd(comment_alice2)
+        .commentAdd(comment_alice2, [], null, no_labels)
         .accounts({
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1570,8 +1799,10 @@ describe("DePress", () => {
         .accounts({
           commentReaction: comment_reaction_pkey,
           comment: comment_pkey,
+          post: post_pkey,
           reactionAuthor: alice.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1612,12 +1843,13 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .commentAdd(another_comment)
+        .commentAdd(another_comment, [], null, no_labels)
         .accounts({
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1634,8 +1866,10 @@ describe("DePress", () => {
         .accounts({
           commentReaction: comment_reaction_pkey,
           comment: comment_pkey,
+          post: post_pkey,
           reactionAuthor: alice.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1688,8 +1922,10 @@ describe("DePress", () => {
           .accounts({
             commentReaction: comment_reaction_pkey,
             comment: comment_pkey,
+            post: post_pkey,
             reactionAuthor: alice.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -1734,8 +1970,10 @@ describe("DePress", () => {
           .accounts({
             commentReaction: comment_reaction_pkey,
             comment: comment_pkey,
+            post: post_pkey,
             reactionAuthor: alice.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
           })
           .signers([alice])
           .rpc({ commitment: "confirmed" });
@@ -1773,12 +2011,13 @@ describe("DePress", () => {
 
       // Create a fresh comment so its reaction PDA is unused
       await program.methods
-        .commentAdd(temp_comment)
+        .commentAdd(temp_comment, [], null, no_labels)
         .accounts({
           commentAuthor: alice.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1795,8 +2034,10 @@ describe("DePress", () => {
         .accounts({
           commentReaction: comment_reaction_pkey,
           comment: comment_pkey,
+          post: post_pkey,
           reactionAuthor: alice.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1808,6 +2049,7 @@ describe("DePress", () => {
           comment: comment_pkey,
           reactionAuthor: alice.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1858,6 +2100,7 @@ describe("DePress", () => {
           comment: comment_pkey,
           reactionAuthor: alice.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1867,8 +2110,10 @@ describe("DePress", () => {
         .accounts({
           commentReaction: comment_reaction_pkey,
           comment: comment_pkey,
+          post: post_pkey,
           reactionAuthor: alice.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
         })
         .signers([alice])
         .rpc({ commitment: "confirmed" });
@@ -1903,6 +2148,7 @@ describe("DePress", () => {
           postReaction: reaction_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -1935,12 +2181,13 @@ describe("DePress", () => {
       );
 
       await program.methods
-        .commentAdd(bob_comment)
+        .commentAdd(bob_comment, [], null, no_labels)
         .accounts({
           commentAuthor: bob.publicKey,
           comment: comment_pkey,
           post: post_pkey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
         })
         .signers([bob])
         .rpc({ commitment: "confirmed" });
@@ -2008,51 +2255,5438 @@ describe("DePress", () => {
       );
     });
   });
-});
 
-async function airdrop(connection: any, address: any, amount = 1000000000) {
-  await connection.confirmTransaction(
-    await connection.requestAirdrop(address, amount),
-    "confirmed"
-  );
-}
+  describe("Hashtags", async () => {
+    const topic_tags1 = "Tagged post";
+    const content_tags1 = "Shipping #Solana programs with #anchor and #solana again";
+    const topic_tags2 = "Another tagged post";
+    const content_tags2 = "More #solana news";
 
-function getCommentAddress(
-  comment_content: string,
-  author: PublicKey,
-  parent_tweet: PublicKey,
-  programID: PublicKey
-) {
-  let hexString = crypto
-    .createHash("sha256")
-    .update(comment_content, "utf-8")
-    .digest("hex");
-  let content_seed = Uint8Array.from(Buffer.from(hexString, "hex"));
+    it("Should store hashtags on the post and create tag accounts", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_tags1,
+        alice.publicKey,
+        program.programId
+      );
+      const [solana_tag] = getTagAddress("solana", program.programId);
+      const [anchor_tag] = getTagAddress("anchor", program.programId);
 
-  return PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode(COMMENT_SEED),
-      author.toBuffer(),
-      content_seed,
-      parent_tweet.toBuffer(),
-    ],
-    programID
-  );
-}
+      await program.methods
+        .postAdd(
+          topic_tags1,
+          content_tags1,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: solana_tag, isSigner: false, isWritable: true },
+          { pubkey: anchor_tag, isSigner: false, isWritable: true },
+        ])
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
 
-function getPostAddress(
-  topic: string,
-  author: PublicKey,
-  programID: PublicKey
-) {
-  return PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode(POST_SEED),
-      anchor.utils.bytes.utf8.encode(topic),
-      author.toBuffer(),
-    ],
-    programID
-  );
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.deepEqual(postData.hashtags, ["solana", "anchor"]);
+
+      const solanaData = await program.account.tag.fetch(solana_tag);
+      assert.strictEqual(solanaData.tag, "solana");
+      assert.strictEqual(solanaData.usageCount.toString(), "1");
+      assert.strictEqual(
+        solanaData.lastPost.toString(),
+        post_pkey.toString()
+      );
+    });
+
+    it("Should increment usage count and track the most recent post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_tags2,
+        charlie.publicKey,
+        program.programId
+      );
+      const [solana_tag] = getTagAddress("solana", program.programId);
+
+      await program.methods
+        .postAdd(
+          topic_tags2,
+          content_tags2,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: charlie.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: solana_tag, isSigner: false, isWritable: true },
+        ])
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const solanaData = await program.account.tag.fetch(solana_tag);
+      assert.strictEqual(solanaData.usageCount.toString(), "2");
+      assert.strictEqual(
+        solanaData.lastPost.toString(),
+        post_pkey.toString()
+      );
+    });
+
+    it("Should fail when tag accounts are missing", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Missing tags",
+          alice.publicKey,
+          program.programId
+        );
+
+        await program.methods
+          .postAdd(
+            "Missing tags",
+            "Where is my #index",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "MissingTagAccount");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Post creation should have failed without tag accounts"
+      );
+    });
+
+    it("Should fail when content has too many hashtags", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Tag spam",
+          alice.publicKey,
+          program.programId
+        );
+
+        await program.methods
+          .postAdd(
+            "Tag spam",
+            "#a #b #c #d #e #f",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TooManyHashtags");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Post creation should have failed with more than 5 hashtags"
+      );
+    });
+
+    it("Should move tag usages along with edits until the post is removed", async () => {
+      const topic_flip = "Changing tags";
+      const [post_pkey] = getPostAddress(
+        topic_flip,
+        alice.publicKey,
+        program.programId
+      );
+      const [a_tag] = getTagAddress("a", program.programId);
+      const [b_tag] = getTagAddress("b", program.programId);
+      const writable = (pubkey: PublicKey) => ({
+        pubkey,
+        isSigner: false,
+        isWritable: true,
+      });
+      const usage = async (pubkey: PublicKey) =>
+        (await program.account.tag.fetch(pubkey)).usageCount.toNumber();
+
+      await program.methods
+        .postAdd(
+          topic_flip,
+          "Starting with #a",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .remainingAccounts([writable(a_tag)])
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      // Removed tags come first, then the added ones
+      const edits: [string, PublicKey[]][] = [
+        ["Switching to #b", [a_tag, b_tag]],
+        ["Back to #a", [b_tag, a_tag]],
+      ];
+      for (const [index, [content, tags]] of edits.entries()) {
+        await program.methods
+          .postEdit(content, null, null)
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            revision: getRevisionAddress(post_pkey, index, program.programId)[0],
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .remainingAccounts(tags.map(writable))
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      }
+      assert.strictEqual(await usage(a_tag), 1);
+      assert.strictEqual(await usage(b_tag), 0);
+
+      await program.methods
+        .postRemove()
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .remainingAccounts([
+          writable(getRevisionAddress(post_pkey, 0, program.programId)[0]),
+          writable(getRevisionAddress(post_pkey, 1, program.programId)[0]),
+          writable(a_tag),
+        ])
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      assert.strictEqual(await usage(a_tag), 0);
+      assert.strictEqual(await usage(b_tag), 0);
+    });
+
+    it("Should drop a tag usage when a tagged post is removed", async () => {
+      const topic_removed = "Short-lived tags";
+      const [post_pkey] = getPostAddress(
+        topic_removed,
+        alice.publicKey,
+        program.programId
+      );
+      const [solana_tag] = getTagAddress("solana", program.programId);
+      const [gone_tag] = getTagAddress("gone_soon", program.programId);
+
+      await program.methods
+        .postAdd(
+          topic_removed,
+          "#gone_soon like most #solana takes",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: gone_tag, isSigner: false, isWritable: true },
+          { pubkey: solana_tag, isSigner: false, isWritable: true },
+        ])
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+      const solanaBefore = await program.account.tag.fetch(solana_tag);
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .postRemove()
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "MissingTagAccount");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Removing a tagged post without its tags should have failed"
+      );
+
+      await program.methods
+        .postRemove()
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: gone_tag, isSigner: false, isWritable: true },
+          { pubkey: solana_tag, isSigner: false, isWritable: true },
+        ])
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const goneData = await program.account.tag.fetch(gone_tag);
+      assert.strictEqual(goneData.usageCount.toString(), "0");
+      const solanaData = await program.account.tag.fetch(solana_tag);
+      assert.strictEqual(
+        solanaData.usageCount.toNumber(),
+        solanaBefore.usageCount.toNumber() - 1
+      );
+    });
+  });
+
+  describe("Mentions", async () => {
+    const topic_mention = "Shout out";
+    const content_mention = "Thanks Alice and Charlie for the review!";
+    const comment_mention = "Bob, Charlie said the same";
+
+    it("Should store mentions on the post and emit a Mentioned event per recipient", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_mention,
+        bob.publicKey,
+        program.programId
+      );
+
+      const tx = await program.methods
+        .postAdd(
+          topic_mention,
+          content_mention,
+          [alice.publicKey, charlie.publicKey],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const mentioned = (await getEvents(program, tx))
+        .filter((event) => event.name === "mentioned")
+        .map((event) => event.data.mentioned.toString());
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.deepEqual(
+        postData.mentions.map((m) => m.toString()),
+        [alice.publicKey.toString(), charlie.publicKey.toString()]
+      );
+      assert.sameMembers(mentioned, [
+        alice.publicKey.toString(),
+        charlie.publicKey.toString(),
+      ]);
+    });
+
+    it("Should store mentions on a comment", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_mention,
+        bob.publicKey,
+        program.programId
+      );
+      const [comment_pkey] = getCommentAddress(
+        comment_mention,
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+
+      await program.methods
+        .commentAdd(
+          comment_mention,
+          [bob.publicKey, charlie.publicKey],
+          null,
+          no_labels
+        )
+        .accounts({
+          commentAuthor: alice.publicKey,
+          comment: comment_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.deepEqual(
+        commentData.mentions.map((m) => m.toString()),
+        [bob.publicKey.toString(), charlie.publicKey.toString()]
+      );
+    });
+
+    it("Should fail when mentioning the same wallet twice", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Double mention",
+          bob.publicKey,
+          program.programId
+        );
+
+        await program.methods
+          .postAdd(
+            "Double mention",
+            "Hi Alice, hi again",
+            [alice.publicKey, alice.publicKey],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "DuplicateMention");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Post creation should have failed with a duplicate mention"
+      );
+    });
+
+    it("Should fail when the author mentions themselves", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Self mention",
+          bob.publicKey,
+          program.programId
+        );
+
+        await program.methods
+          .postAdd(
+            "Self mention",
+            "Talking to myself",
+            [bob.publicKey],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "SelfMention");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Post creation should have failed with a self mention"
+      );
+    });
+
+    it("Should fail when mentioning more than 5 wallets", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Mention spam",
+          bob.publicKey,
+          program.programId
+        );
+        const mentions = Array.from(
+          { length: 6 },
+          () => anchor.web3.Keypair.generate().publicKey
+        );
+
+        await program.methods
+          .postAdd(
+            "Mention spam",
+            "Hello everyone",
+            mentions,
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TooManyMentions");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Post creation should have failed with more than 5 mentions"
+      );
+    });
+  });
+
+  describe("Reposts", async () => {
+    const topic_original = "Worth sharing";
+    const content_original = "Something everyone should read";
+
+    it("Should successfully repost a post and increment its repost count", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_original,
+        bob.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_original,
+          content_original,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const [repost_pkey, repost_bump] = getRepostAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .repostAdd()
+        .accounts({
+          reposter: alice.publicKey,
+          repost: repost_pkey,
+          originalPost: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const repostData = await program.account.repost.fetch(repost_pkey);
+      assert.strictEqual(
+        repostData.reposter.toString(),
+        alice.publicKey.toString()
+      );
+      assert.strictEqual(
+        repostData.originalPost.toString(),
+        post_pkey.toString()
+      );
+      assert.strictEqual(repostData.bump, repost_bump);
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.repostCount, 1);
+      assert.strictEqual(
+        repostData.originalGlobalId.toString(),
+        postData.globalId.toString()
+      );
+    });
+
+    it("Should fail when reposting the same post twice", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_original,
+          bob.publicKey,
+          program.programId
+        );
+        const [repost_pkey] = getRepostAddress(
+          alice.publicKey,
+          post_pkey,
+          program.programId
+        );
+        await program.methods
+          .repostAdd()
+          .accounts({
+            reposter: alice.publicKey,
+            repost: repost_pkey,
+            originalPost: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        should_fail = "Failed";
+        assert.isTrue(
+          SolanaError.contains(error.logs, "already in use"),
+          "Expected 'already in use' error for duplicate repost"
+        );
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Reposting the same post twice should have failed"
+      );
+    });
+
+    it("Should successfully remove a repost and decrement the repost count", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_original,
+        bob.publicKey,
+        program.programId
+      );
+      const [repost_pkey] = getRepostAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .repostRemove()
+        .accounts({
+          reposter: alice.publicKey,
+          repost: repost_pkey,
+          originalPost: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const repostInfo = await provider.connection.getAccountInfo(repost_pkey);
+      assert.isNull(repostInfo, "Repost account should be closed");
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.repostCount, 0);
+    });
+
+    it("Should successfully create a quote post referencing the original", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_original,
+        bob.publicKey,
+        program.programId
+      );
+      const [quote_pkey] = getPostAddress(
+        "My take",
+        charlie.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          "My take",
+          "Agree with every word of this",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: charlie.publicKey,
+          post: quote_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          quotedPost: post_pkey,
+          payer: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const quoteData = await program.account.post.fetch(quote_pkey);
+      assert.strictEqual(
+        quoteData.quotedPost.toString(),
+        post_pkey.toString()
+      );
+    });
+
+    it("Should only close orphaned reposts once the original post is removed", async () => {
+      const topic_short_lived = "Short lived";
+      const [post_pkey] = getPostAddress(
+        topic_short_lived,
+        bob.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_short_lived,
+          "Going away soon",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const [repost_pkey] = getRepostAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .repostAdd()
+        .accounts({
+          reposter: alice.publicKey,
+          repost: repost_pkey,
+          originalPost: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .repostRemoveOrphaned()
+          .accounts({
+            repost: repost_pkey,
+            originalPost: post_pkey,
+            reposter: alice.publicKey,
+            caller: charlie.publicKey,
+            payer: alice.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "OriginalPostExists");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Closing a repost of a live post should have failed"
+      );
+
+      await program.methods
+        .postRemove()
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const balanceBefore = await provider.connection.getBalance(
+        alice.publicKey
+      );
+      await program.methods
+        .repostRemoveOrphaned()
+        .accounts({
+          repost: repost_pkey,
+          originalPost: post_pkey,
+          reposter: alice.publicKey,
+          caller: charlie.publicKey,
+          payer: alice.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const repostInfo = await provider.connection.getAccountInfo(repost_pkey);
+      assert.isNull(repostInfo, "Orphaned repost account should be closed");
+      const balanceAfter = await provider.connection.getBalance(
+        alice.publicKey
+      );
+      assert.isAbove(
+        balanceAfter,
+        balanceBefore,
+        "Rent should be refunded to the payer"
+      );
+    });
+
+    it("Should fail to repost or quote a draft", async () => {
+      const topic_draft = "Not ready yet";
+      const [draft_pkey] = getPostAddress(
+        topic_draft,
+        bob.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_draft,
+          "Still writing",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          true,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: draft_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      let should_fail = "This Should Fail";
+      try {
+        const [repost_pkey] = getRepostAddress(
+          alice.publicKey,
+          draft_pkey,
+          program.programId
+        );
+        await program.methods
+          .repostAdd()
+          .accounts({
+            reposter: alice.publicKey,
+            repost: repost_pkey,
+            originalPost: draft_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotPublished");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Reposting a draft should have failed"
+      );
+
+      should_fail = "This Should Fail";
+      try {
+        const [quote_pkey] = getPostAddress(
+          "Sneak peek",
+          charlie.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            "Sneak peek",
+            "Quoting what is not out yet",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: charlie.publicKey,
+            post: quote_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            quotedPost: draft_pkey,
+            payer: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotPublished");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Quoting a draft should have failed"
+      );
+    });
+
+    it("Should not count reposts of a removed post against one re-created at its address", async () => {
+      const topic_recreated = "Said it twice";
+      const [post_pkey] = getPostAddress(
+        topic_recreated,
+        bob.publicKey,
+        program.programId
+      );
+      const addPost = () =>
+        program.methods
+          .postAdd(
+            topic_recreated,
+            "Worth repeating",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      const addRepost = (reposter: anchor.web3.Keypair) =>
+        program.methods
+          .repostAdd()
+          .accounts({
+            reposter: reposter.publicKey,
+            repost: getRepostAddress(
+              reposter.publicKey,
+              post_pkey,
+              program.programId
+            )[0],
+            originalPost: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: reposter.publicKey,
+          })
+          .signers([reposter])
+          .rpc({ commitment: "confirmed" });
+
+      await addPost();
+      await addRepost(alice);
+      await addRepost(charlie);
+      await program.methods
+        .postRemove()
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+      await addPost();
+
+      // The reposter undoing it leaves the new post's count alone
+      await program.methods
+        .repostRemove()
+        .accounts({
+          reposter: alice.publicKey,
+          repost: getRepostAddress(
+            alice.publicKey,
+            post_pkey,
+            program.programId
+          )[0],
+          originalPost: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+      let postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.repostCount, 0);
+
+      // And anyone may close the other one as orphaned
+      const [charlie_repost] = getRepostAddress(
+        charlie.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .repostRemoveOrphaned()
+        .accounts({
+          repost: charlie_repost,
+          originalPost: post_pkey,
+          reposter: charlie.publicKey,
+          caller: alice.publicKey,
+          payer: charlie.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+      assert.isNull(
+        await provider.connection.getAccountInfo(charlie_repost),
+        "Orphaned repost account should be closed"
+      );
+
+      // Reposting the new post counts again
+      await addRepost(alice);
+      postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.repostCount, 1);
+    });
+  });
+
+  describe("Polls", async () => {
+    const topic_poll = "Pizza poll";
+    const options = ["Pineapple", "No pineapple", "Don't care"];
+
+    it("Should successfully attach a poll to a post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_poll,
+        alice.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_poll,
+          "Settle this once and for all",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const [poll_pkey, poll_bump] = getPollAddress(
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .pollAdd(options, null)
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          poll: poll_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const pollData = await program.account.poll.fetch(poll_pkey);
+      assert.deepEqual(pollData.options, options);
+      assert.deepEqual(
+        pollData.tallies.map((t) => t.toNumber()),
+        [0, 0, 0]
+      );
+      assert.isNull(pollData.closesAt);
+      assert.strictEqual(pollData.bump, poll_bump);
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.isTrue(postData.hasPoll);
+    });
+
+    it("Should fail when someone else attaches a poll to the post", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_bob1,
+          bob.publicKey,
+          program.programId
+        );
+        const [poll_pkey] = getPollAddress(post_pkey, program.programId);
+        await program.methods
+          .pollAdd(options, null)
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            poll: poll_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidOwner");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Only the post author should be able to attach a poll"
+      );
+    });
+
+    it("Should record a vote and update the tally", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_poll,
+        alice.publicKey,
+        program.programId
+      );
+      const [poll_pkey] = getPollAddress(post_pkey, program.programId);
+      const [vote_pkey] = getPollVoteAddress(
+        bob.publicKey,
+        poll_pkey,
+        program.programId
+      );
+
+      await program.methods
+        .pollVote(1)
+        .accounts({
+          voter: bob.publicKey,
+          poll: poll_pkey,
+          post: post_pkey,
+          pollVote: vote_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const voteData = await program.account.pollVote.fetch(vote_pkey);
+      assert.strictEqual(voteData.optionIndex, 1);
+      assert.strictEqual(voteData.payer.toString(), bob.publicKey.toString());
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(
+        voteData.postGlobalId.toString(),
+        postData.globalId.toString()
+      );
+      const pollData = await program.account.poll.fetch(poll_pkey);
+      assert.deepEqual(
+        pollData.tallies.map((t) => t.toNumber()),
+        [0, 1, 0]
+      );
+    });
+
+    it("Should fail when voting twice on the same poll", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_poll,
+          alice.publicKey,
+          program.programId
+        );
+        const [poll_pkey] = getPollAddress(post_pkey, program.programId);
+        const [vote_pkey] = getPollVoteAddress(
+          bob.publicKey,
+          poll_pkey,
+          program.programId
+        );
+        await program.methods
+          .pollVote(0)
+          .accounts({
+            voter: bob.publicKey,
+            poll: poll_pkey,
+            post: post_pkey,
+            pollVote: vote_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        should_fail = "Failed";
+        assert.isTrue(
+          SolanaError.contains(error.logs, "already in use"),
+          "Expected 'already in use' error for duplicate vote"
+        );
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Voting twice should have failed"
+      );
+    });
+
+    it("Should fail when voting for a non-existent option", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_poll,
+          alice.publicKey,
+          program.programId
+        );
+        const [poll_pkey] = getPollAddress(post_pkey, program.programId);
+        const [vote_pkey] = getPollVoteAddress(
+          charlie.publicKey,
+          poll_pkey,
+          program.programId
+        );
+        await program.methods
+          .pollVote(3)
+          .accounts({
+            voter: charlie.publicKey,
+            poll: poll_pkey,
+            post: post_pkey,
+            pollVote: vote_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidPollOption");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Voting for an out of range option should have failed"
+      );
+    });
+
+    it("Should fail to remove a post with a poll without its poll account", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_poll,
+          alice.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postRemove()
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PollAccountRequired");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Removing a post without closing its poll should have failed"
+      );
+    });
+
+    it("Should close the poll when its parent post is removed", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_poll,
+        alice.publicKey,
+        program.programId
+      );
+      const [poll_pkey] = getPollAddress(post_pkey, program.programId);
+      await program.methods
+        .postRemove()
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          poll: poll_pkey,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const pollInfo = await provider.connection.getAccountInfo(poll_pkey);
+      assert.isNull(pollInfo, "Poll account should be closed");
+    });
+
+    it("Should close votes on a removed poll, also once one is re-created at its address", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_poll,
+        alice.publicKey,
+        program.programId
+      );
+      const [poll_pkey] = getPollAddress(post_pkey, program.programId);
+      const [vote_pkey] = getPollVoteAddress(
+        bob.publicKey,
+        poll_pkey,
+        program.programId
+      );
+
+      // Re-create the post and its poll at the same addresses
+      await program.methods
+        .postAdd(
+          topic_poll,
+          "Round two",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .pollAdd(options, null)
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          poll: poll_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const balanceBefore = await provider.connection.getBalance(
+        bob.publicKey
+      );
+      await program.methods
+        .pollVoteRemoveOrphaned()
+        .accounts({
+          pollVote: vote_pkey,
+          poll: poll_pkey,
+          post: post_pkey,
+          voter: bob.publicKey,
+          payer: bob.publicKey,
+          caller: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      assert.isNull(
+        await provider.connection.getAccountInfo(vote_pkey),
+        "Orphaned poll vote should be closed"
+      );
+      assert.isAbove(
+        await provider.connection.getBalance(bob.publicKey),
+        balanceBefore,
+        "Rent should be refunded to the payer"
+      );
+
+      // Voting on the new poll is open again, and its votes stay
+      await program.methods
+        .pollVote(0)
+        .accounts({
+          voter: bob.publicKey,
+          poll: poll_pkey,
+          post: post_pkey,
+          pollVote: vote_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .pollVoteRemoveOrphaned()
+          .accounts({
+            pollVote: vote_pkey,
+            poll: poll_pkey,
+            post: post_pkey,
+            voter: bob.publicKey,
+            payer: bob.publicKey,
+            caller: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PollExists");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Closing a vote on a live poll should have failed"
+      );
+    });
+
+    it("Should fail to vote on the poll of a draft", async () => {
+      const topic_draft_poll = "Draft poll";
+      const [post_pkey] = getPostAddress(
+        topic_draft_poll,
+        alice.publicKey,
+        program.programId
+      );
+      const [poll_pkey] = getPollAddress(post_pkey, program.programId);
+      await program.methods
+        .postAdd(
+          topic_draft_poll,
+          "Not asked yet",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          true,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .pollAdd(options, null)
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          poll: poll_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .pollVote(0)
+          .accounts({
+            voter: bob.publicKey,
+            poll: poll_pkey,
+            post: post_pkey,
+            pollVote: getPollVoteAddress(
+              bob.publicKey,
+              poll_pkey,
+              program.programId
+            )[0],
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotPublished");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Voting on the poll of a draft should have failed"
+      );
+    });
+  });
+
+  describe("Pinned Posts", async () => {
+    const topic_pinned = "Pin me";
+
+    it("Should pin a post and create the author's profile", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_pinned,
+        charlie.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_pinned,
+          "My best post ever",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: charlie.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const [profile_pkey, profile_bump] = getProfileAddress(
+        charlie.publicKey,
+        program.programId
+      );
+      await program.methods
+        .pinPost()
+        .accounts({
+          postAuthor: charlie.publicKey,
+          post: post_pkey,
+          profile: profile_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const profileData = await program.account.profile.fetch(profile_pkey);
+      assert.strictEqual(
+        profileData.author.toString(),
+        charlie.publicKey.toString()
+      );
+      assert.deepEqual(
+        profileData.pinnedPosts.map((p) => p.toString()),
+        [post_pkey.toString()]
+      );
+      assert.strictEqual(profileData.bump, profile_bump);
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.isTrue(postData.isPinned);
+    });
+
+    it("Should fail when pinning the same post twice", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_pinned,
+          charlie.publicKey,
+          program.programId
+        );
+        const [profile_pkey] = getProfileAddress(
+          charlie.publicKey,
+          program.programId
+        );
+        await program.methods
+          .pinPost()
+          .accounts({
+            postAuthor: charlie.publicKey,
+            post: post_pkey,
+            profile: profile_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostAlreadyPinned");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Pinning the same post twice should have failed"
+      );
+    });
+
+    it("Should fail when pinning another user's post", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_bob1,
+          bob.publicKey,
+          program.programId
+        );
+        const [profile_pkey] = getProfileAddress(
+          charlie.publicKey,
+          program.programId
+        );
+        await program.methods
+          .pinPost()
+          .accounts({
+            postAuthor: charlie.publicKey,
+            post: post_pkey,
+            profile: profile_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidOwner");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Pinning another user's post should have failed"
+      );
+    });
+
+    it("Should unpin a post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_pinned,
+        charlie.publicKey,
+        program.programId
+      );
+      const [profile_pkey] = getProfileAddress(
+        charlie.publicKey,
+        program.programId
+      );
+      await program.methods
+        .unpinPost()
+        .accounts({
+          postAuthor: charlie.publicKey,
+          post: post_pkey,
+          profile: profile_pkey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const profileData = await program.account.profile.fetch(profile_pkey);
+      assert.lengthOf(profileData.pinnedPosts, 0);
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.isFalse(postData.isPinned);
+    });
+
+    it("Should drop the pin when the pinned post is removed", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_pinned,
+        charlie.publicKey,
+        program.programId
+      );
+      const [profile_pkey] = getProfileAddress(
+        charlie.publicKey,
+        program.programId
+      );
+      await program.methods
+        .pinPost()
+        .accounts({
+          postAuthor: charlie.publicKey,
+          post: post_pkey,
+          profile: profile_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      await program.methods
+        .postRemove()
+        .accounts({
+          postAuthor: charlie.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          profile: profile_pkey,
+          payer: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const profileData = await program.account.profile.fetch(profile_pkey);
+      assert.lengthOf(profileData.pinnedPosts, 0);
+    });
+  });
+
+  describe("Post Revisions", async () => {
+    const topic_edit = "Editable";
+    const content_v1 = "First draft";
+    const content_v2 = "Second draft with a #fix";
+
+    it("Should store the previous content as a revision when editing", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_edit,
+        bob.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_edit,
+          content_v1,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const [revision_pkey, revision_bump] = getRevisionAddress(
+        post_pkey,
+        0,
+        program.programId
+      );
+      const [fix_tag] = getTagAddress("fix", program.programId);
+      await program.methods
+        .postEdit(content_v2, null, null)
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          revision: revision_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: fix_tag, isSigner: false, isWritable: true },
+        ])
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const revisionData = await program.account.postRevision.fetch(
+        revision_pkey
+      );
+      assert.strictEqual(revisionData.previousContent, content_v1);
+      assert.isNull(revisionData.previousContentRef);
+      assert.strictEqual(revisionData.revisionNumber, 0);
+      assert.strictEqual(revisionData.bump, revision_bump);
+      assert.isAbove(revisionData.editedAt.toNumber(), 0);
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.content, content_v2);
+      assert.strictEqual(postData.revisionCount, 1);
+      assert.deepEqual(postData.hashtags, ["fix"]);
+
+      const tagData = await program.account.tag.fetch(fix_tag);
+      assert.strictEqual(tagData.usageCount.toString(), "1");
+    });
+
+    it("Should fail when another user edits the post", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_edit,
+          bob.publicKey,
+          program.programId
+        );
+        const [revision_pkey] = getRevisionAddress(
+          post_pkey,
+          1,
+          program.programId
+        );
+        await program.methods
+          .postEdit("Hijacked", null, null)
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            revision: revision_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Editing another user's post should have failed"
+      );
+    });
+
+    it("Should prune a revision and refund its rent", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_edit,
+        bob.publicKey,
+        program.programId
+      );
+      const [revision_pkey] = getRevisionAddress(
+        post_pkey,
+        0,
+        program.programId
+      );
+      await program.methods
+        .revisionPrune()
+        .accounts({
+          postAuthor: bob.publicKey,
+          revision: revision_pkey,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const revisionInfo = await provider.connection.getAccountInfo(
+        revision_pkey
+      );
+      assert.isNull(revisionInfo, "Revision account should be closed");
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.revisionCount, 1);
+    });
+
+    it("Should close the revisions of a removed post so a re-created one starts over", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_edit,
+        bob.publicKey,
+        program.programId
+      );
+      const [pruned_pkey] = getRevisionAddress(
+        post_pkey,
+        0,
+        program.programId
+      );
+      const [revision_pkey] = getRevisionAddress(
+        post_pkey,
+        1,
+        program.programId
+      );
+
+      // Sponsored by alice, who gets the rent back on removal
+      await program.methods
+        .postEdit("Third draft", null, null)
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          revision: revision_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .remainingAccounts([
+          {
+            pubkey: getTagAddress("fix", program.programId)[0],
+            isSigner: false,
+            isWritable: true,
+          },
+        ])
+        .signers([bob, alice])
+        .rpc({ commitment: "confirmed" });
+
+      const balanceBefore = await provider.connection.getBalance(
+        alice.publicKey
+      );
+      await program.methods
+        .postRemove()
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: pruned_pkey, isSigner: false, isWritable: false },
+          { pubkey: revision_pkey, isSigner: false, isWritable: true },
+          { pubkey: alice.publicKey, isSigner: false, isWritable: true },
+        ])
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      assert.isNull(
+        await provider.connection.getAccountInfo(revision_pkey),
+        "Revision account should be closed"
+      );
+      assert.isAbove(
+        await provider.connection.getBalance(alice.publicKey),
+        balanceBefore,
+        "Rent should be refunded to the revision's payer"
+      );
+
+      await program.methods
+        .postAdd(
+          topic_edit,
+          content_v1,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .postEdit("Edited again", null, null)
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          revision: pruned_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const revisionData = await program.account.postRevision.fetch(
+        pruned_pkey
+      );
+      assert.strictEqual(revisionData.revisionNumber, 0);
+      assert.strictEqual(revisionData.previousContent, content_v1);
+    });
+  });
+
+  describe("Long-form Posts", async () => {
+    const topic_article = "Release notes";
+    const intro = "Release notes for v2, see below.";
+    const chunk0 = "A".repeat(900);
+    const chunk1 = "B".repeat(450);
+
+    it("Should append chunks and track the total length", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_article,
+        alice.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_article,
+          intro,
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      for (const [index, chunk] of [chunk0, chunk1].entries()) {
+        const [chunk_pkey] = getChunkAddress(
+          post_pkey,
+          index,
+          program.programId
+        );
+        await program.methods
+          .chunkAppend(chunk)
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            chunk: chunk_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      }
+
+      const [chunk1_pkey] = getChunkAddress(post_pkey, 1, program.programId);
+      const chunkData = await program.account.postChunk.fetch(chunk1_pkey);
+      assert.strictEqual(chunkData.index, 1);
+      assert.strictEqual(chunkData.content, chunk1);
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.chunkCount, 2);
+      assert.strictEqual(
+        postData.totalLength,
+        intro.length + chunk0.length + chunk1.length
+      );
+    });
+
+    it("Should fail to append a chunk longer than 900 bytes", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_article,
+          alice.publicKey,
+          program.programId
+        );
+        const [chunk_pkey] = getChunkAddress(post_pkey, 2, program.programId);
+        await program.methods
+          .chunkAppend("C".repeat(901))
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            chunk: chunk_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Appending an oversized chunk should have failed"
+      );
+    });
+
+    it("Should reject new chunks once the post is finalized", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_article,
+        alice.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postFinalize()
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.isTrue(postData.finalized);
+
+      let should_fail = "This Should Fail";
+      try {
+        const [chunk_pkey] = getChunkAddress(post_pkey, 2, program.programId);
+        await program.methods
+          .chunkAppend("Too late")
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            chunk: chunk_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostFinalized");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Appending to a finalized post should have failed"
+      );
+    });
+
+    it("Should fail to remove the post without its chunk accounts", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          topic_article,
+          alice.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postRemove()
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "MissingChunkAccount");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Removing a long-form post without its chunks should have failed"
+      );
+    });
+
+    it("Should close all chunks when the post is removed", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_article,
+        alice.publicKey,
+        program.programId
+      );
+      const chunks = [0, 1].map(
+        (index) => getChunkAddress(post_pkey, index, program.programId)[0]
+      );
+      await program.methods
+        .postRemove()
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .remainingAccounts(
+          chunks.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      for (const chunk of chunks) {
+        const chunkInfo = await provider.connection.getAccountInfo(chunk);
+        assert.isNull(chunkInfo, "Chunk account should be closed");
+      }
+    });
+  });
+
+  describe("Off-chain Content", async () => {
+    const body = "A very long article stored on Arweave";
+    const content_hash = Array.from(
+      crypto.createHash("sha256").update(body, "utf-8").digest()
+    );
+    const content_ref = {
+      uri: "ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U",
+      contentHash: content_hash,
+      mimeType: "text/markdown",
+      byteLength: new anchor.BN(Buffer.byteLength(body)),
+    };
+
+    it("Should create a post referencing off-chain content", async () => {
+      const [post_pkey] = getPostAddress(
+        "Off-chain article",
+        bob.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          "Off-chain article",
+          "",
+          [],
+          content_ref,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.content, "");
+      assert.strictEqual(postData.contentRef.uri, content_ref.uri);
+      assert.deepEqual(postData.contentRef.contentHash, content_hash);
+      assert.strictEqual(postData.contentRef.mimeType, "text/markdown");
+      assert.strictEqual(
+        postData.contentRef.byteLength.toString(),
+        content_ref.byteLength.toString()
+      );
+    });
+
+    it("Should fail when an off-chain post also has inline content", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Both kinds",
+          bob.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            "Both kinds",
+            "Inline too",
+            [],
+            content_ref,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InlineContentNotAllowed");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Off-chain posts with inline content should have failed"
+      );
+    });
+
+    it("Should fail with an unsupported URI scheme", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Bad scheme",
+          bob.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            "Bad scheme",
+            "",
+            [],
+            { ...content_ref, uri: "ftp://example.com/article.md" },
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidUriScheme");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Off-chain posts with an unsupported URI scheme should have failed"
+      );
+    });
+
+    it("Should fail with an invalid MIME type", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Bad mime",
+          bob.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            "Bad mime",
+            "",
+            [],
+            { ...content_ref, mimeType: "markdown" },
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidMimeType");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Off-chain posts with an invalid MIME type should have failed"
+      );
+    });
+
+    it("Should replace the content reference on edit, keeping the previous one", async () => {
+      const [post_pkey] = getPostAddress(
+        "Off-chain article",
+        bob.publicKey,
+        program.programId
+      );
+      const [revision_pkey] = getRevisionAddress(
+        post_pkey,
+        0,
+        program.programId
+      );
+      const new_body = "A revised article stored on IPFS";
+      const new_ref = {
+        uri: "ipfs://bafkreidgvpkjawlxz6sffxzwgooowe5yt7i6wsyg236mfoks77nywkptdy",
+        contentHash: Array.from(
+          crypto.createHash("sha256").update(new_body, "utf-8").digest()
+        ),
+        mimeType: "text/markdown",
+        byteLength: new anchor.BN(Buffer.byteLength(new_body)),
+      };
+      await program.methods
+        .postEdit("", null, new_ref)
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          revision: revision_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.content, "");
+      assert.strictEqual(postData.contentRef.uri, new_ref.uri);
+      assert.deepEqual(postData.contentRef.contentHash, new_ref.contentHash);
+      assert.strictEqual(
+        postData.contentRef.byteLength.toString(),
+        new_ref.byteLength.toString()
+      );
+
+      const revisionData = await program.account.postRevision.fetch(
+        revision_pkey
+      );
+      assert.strictEqual(revisionData.previousContent, "");
+      assert.strictEqual(revisionData.previousContentRef.uri, content_ref.uri);
+      assert.deepEqual(
+        revisionData.previousContentRef.contentHash,
+        content_hash
+      );
+    });
+
+    it("Should fail to edit in a new content reference with inline content", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Off-chain article",
+          bob.publicKey,
+          program.programId
+        );
+        const [revision_pkey] = getRevisionAddress(
+          post_pkey,
+          1,
+          program.programId
+        );
+        await program.methods
+          .postEdit("Inline too", null, content_ref)
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            revision: revision_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InlineContentNotAllowed");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Editing in a content reference with inline content should have failed"
+      );
+    });
+  });
+
+  describe("Attachments", async () => {
+    const image = {
+      uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+      mimeType: "image/png",
+      byteSize: new anchor.BN(48213),
+      contentHash: Array.from(
+        crypto.createHash("sha256").update("image bytes").digest()
+      ),
+      altText: "A cat sitting on a keyboard",
+    };
+
+    it("Should store attachments on a post", async () => {
+      const [post_pkey] = getPostAddress(
+        "Photo dump",
+        charlie.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          "Photo dump",
+          "Look at this",
+          [],
+          null,
+          [image, image],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: charlie.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.lengthOf(postData.attachments, 2);
+      assert.strictEqual(postData.attachments[0].uri, image.uri);
+      assert.strictEqual(postData.attachments[0].mimeType, image.mimeType);
+      assert.strictEqual(postData.attachments[0].altText, image.altText);
+      assert.deepEqual(postData.attachments[0].contentHash, image.contentHash);
+    });
+
+    it("Should store an attachment on a comment", async () => {
+      const [post_pkey] = getPostAddress(
+        "Photo dump",
+        charlie.publicKey,
+        program.programId
+      );
+      const comment = "Here is mine";
+      const [comment_pkey] = getCommentAddress(
+        comment,
+        bob.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .commentAdd(comment, [], image, no_labels)
+        .accounts({
+          commentAuthor: bob.publicKey,
+          comment: comment_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.strictEqual(commentData.attachment.uri, image.uri);
+      assert.strictEqual(
+        commentData.attachment.byteSize.toString(),
+        image.byteSize.toString()
+      );
+    });
+
+    it("Should fail with more than 4 attachments", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Too many photos",
+          charlie.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            "Too many photos",
+            "",
+            [],
+            null,
+            Array(5).fill(image),
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: charlie.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TooManyAttachments");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Posts with more than 4 attachments should have failed"
+      );
+    });
+
+    it("Should fail when alt text exceeds 100 bytes", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Verbose alt",
+          charlie.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            "Verbose alt",
+            "",
+            [],
+            null,
+            [{ ...image, altText: "x".repeat(101) }],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: charlie.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "AltTextTooLong");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Attachments with oversized alt text should have failed"
+      );
+    });
+  });
+
+  describe("Content Labels", async () => {
+    const topic_labels = "Finale thoughts";
+    const labels = {
+      language: "pt-BR",
+      contentWarning: "Spoilers for the season finale",
+      flags: FLAG_SPOILER,
+    };
+
+    it("Should store labels on a post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_labels,
+        alice.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_labels,
+          "Que final!",
+          [],
+          null,
+          [],
+          labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.labels.language, "pt-BR");
+      assert.strictEqual(
+        postData.labels.contentWarning,
+        labels.contentWarning
+      );
+      assert.strictEqual(postData.labels.flags, FLAG_SPOILER);
+    });
+
+    it("Should update labels when editing the post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_labels,
+        alice.publicKey,
+        program.programId
+      );
+      const [revision_pkey] = getRevisionAddress(
+        post_pkey,
+        0,
+        program.programId
+      );
+      await program.methods
+        .postEdit(
+          "Que final!",
+          {
+            ...labels,
+            flags: FLAG_SPOILER | FLAG_AI_GENERATED,
+          },
+          null
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          revision: revision_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(
+        postData.labels.flags,
+        FLAG_SPOILER | FLAG_AI_GENERATED
+      );
+      assert.strictEqual(postData.labels.language, "pt-BR");
+    });
+
+    it("Should store and edit labels on a comment", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_labels,
+        alice.publicKey,
+        program.programId
+      );
+      const comment = "Nao acredito";
+      const [comment_pkey] = getCommentAddress(
+        comment,
+        charlie.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .commentAdd(comment, [], null, { ...labels, contentWarning: "" })
+        .accounts({
+          commentAuthor: charlie.publicKey,
+          comment: comment_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      await program.methods
+        .commentLabelsEdit({
+          language: "pt",
+          contentWarning: "",
+          flags: FLAG_NSFW,
+        })
+        .accounts({
+          commentAuthor: charlie.publicKey,
+          comment: comment_pkey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.strictEqual(commentData.labels.language, "pt");
+      assert.strictEqual(commentData.labels.flags, FLAG_NSFW);
+    });
+
+    it("Should fail with a malformed language tag", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Bad language",
+          alice.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            "Bad language",
+            "Hello",
+            [],
+            null,
+            [],
+            { ...no_labels, language: "english_us" },
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidLanguageTag");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Posts with a malformed language tag should have failed"
+      );
+    });
+
+    it("Should fail with unknown content flags", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Bad flags",
+          alice.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            "Bad flags",
+            "Hello",
+            [],
+            null,
+            [],
+            { ...no_labels, flags: 1 << 7 },
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidContentFlags");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Posts with unknown flags should have failed"
+      );
+    });
+  });
+
+  describe("Scheduled Publishing", async () => {
+    const topic_scheduled = "Launch day";
+
+    it("Should fail to schedule a post in the past", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Too late",
+          bob.publicKey,
+          program.programId
+        );
+        const now = await getChainTime(provider.connection);
+        await program.methods
+          .postAdd(
+            "Too late",
+            "Yesterday's news",
+            [],
+            null,
+            [],
+            no_labels,
+            new anchor.BN(now - 60),
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidPublishTime");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Scheduling a post in the past should have failed"
+      );
+    });
+
+    it("Should reject engagement before the publishing time", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_scheduled,
+        bob.publicKey,
+        program.programId
+      );
+      const now = await getChainTime(provider.connection);
+      await program.methods
+        .postAdd(
+          topic_scheduled,
+          "We are live!",
+          [],
+          null,
+          [],
+          no_labels,
+          new anchor.BN(now + 5),
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.isFalse(postData.published);
+
+      let should_fail = "This Should Fail";
+      try {
+        const [reaction_pkey] = getPostReactionAddress(
+          alice.publicKey,
+          post_pkey,
+          program.programId
+        );
+        await program.methods
+          .likePost()
+          .accounts({
+            reactionAuthor: alice.publicKey,
+            postReaction: reaction_pkey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotPublished");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Reacting before the publishing time should have failed"
+      );
+
+      should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .publishDue()
+          .accounts({ post: post_pkey })
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotPublished");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Publishing before the publishing time should have failed"
+      );
+    });
+
+    it("Should publish the post once due and accept engagement", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_scheduled,
+        bob.publicKey,
+        program.programId
+      );
+      const postData = await program.account.post.fetch(post_pkey);
+      while (
+        (await getChainTime(provider.connection)) <
+        postData.publishAt.toNumber()
+      ) {
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+      }
+
+      await program.methods
+        .publishDue()
+        .accounts({ post: post_pkey })
+        .rpc({ commitment: "confirmed" });
+
+      const publishedData = await program.account.post.fetch(post_pkey);
+      assert.isTrue(publishedData.published);
+
+      const [reaction_pkey] = getPostReactionAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .likePost()
+        .accounts({
+          reactionAuthor: alice.publicKey,
+          postReaction: reaction_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      await checkPost(
+        program,
+        post_pkey,
+        bob.publicKey,
+        topic_scheduled,
+        null,
+        1
+      );
+    });
+  });
+
+  describe("Ephemeral Posts", async () => {
+    const topic_ephemeral = "Flash sale";
+
+    it("Should fail to create a post that expires in the past", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        const [post_pkey] = getPostAddress(
+          "Already gone",
+          bob.publicKey,
+          program.programId
+        );
+        const now = await getChainTime(provider.connection);
+        await program.methods
+          .postAdd(
+            "Already gone",
+            "Blink and you miss it",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            new anchor.BN(now - 60),
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidExpiryTime");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Creating an already expired post should have failed"
+      );
+    });
+
+    it("Should not close a post before it expires", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_ephemeral,
+        bob.publicKey,
+        program.programId
+      );
+      const now = await getChainTime(provider.connection);
+      await program.methods
+        .postAdd(
+          topic_ephemeral,
+          "Half price for the next few seconds",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          new anchor.BN(now + 5),
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.expiresAt.toNumber(), now + 5);
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .closeExpiredPost()
+          .accounts({
+            post: post_pkey,
+            postAuthor: bob.publicKey,
+            caller: alice.publicKey,
+            payer: bob.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotExpired");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Closing a post before its expiry should have failed"
+      );
+    });
+
+    it("Should reject reactions once the post has expired", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_ephemeral,
+        bob.publicKey,
+        program.programId
+      );
+      const postData = await program.account.post.fetch(post_pkey);
+      while (
+        (await getChainTime(provider.connection)) <
+        postData.expiresAt.toNumber()
+      ) {
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+      }
+
+      let should_fail = "This Should Fail";
+      try {
+        const [reaction_pkey] = getPostReactionAddress(
+          alice.publicKey,
+          post_pkey,
+          program.programId
+        );
+        await program.methods
+          .likePost()
+          .accounts({
+            reactionAuthor: alice.publicKey,
+            postReaction: reaction_pkey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostExpired");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Reacting to an expired post should have failed"
+      );
+    });
+
+    it("Should let anyone close an expired post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_ephemeral,
+        bob.publicKey,
+        program.programId
+      );
+      const postRent = await provider.connection.getBalance(post_pkey);
+      const authorBalance = await provider.connection.getBalance(
+        bob.publicKey
+      );
+
+      await program.methods
+        .closeExpiredPost()
+        .accounts({
+          post: post_pkey,
+          postAuthor: bob.publicKey,
+          caller: alice.publicKey,
+          payer: bob.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postInfo = await provider.connection.getAccountInfo(post_pkey);
+      assert.isNull(postInfo);
+      assert.strictEqual(
+        await provider.connection.getBalance(bob.publicKey),
+        authorBalance + postRent
+      );
+    });
+  });
+
+  describe("Drafts", async () => {
+    const topic_draft = "Work in progress";
+
+    it("Should save a draft without indexing or accepting engagement", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_draft,
+        alice.publicKey,
+        program.programId
+      );
+      // No tag account is passed: drafts are not indexed
+      await program.methods
+        .postAdd(
+          topic_draft,
+          "First sketch #drafting",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          true,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.isTrue(postData.isDraft);
+      assert.isFalse(postData.published);
+
+      // Indexers filter on the raw byte
+      const postInfo = await provider.connection.getAccountInfo(post_pkey);
+      assert.strictEqual(postInfo.data[POST_PUBLISHED_OFFSET], 0);
+
+      let should_fail = "This Should Fail";
+      try {
+        const [reaction_pkey] = getPostReactionAddress(
+          bob.publicKey,
+          post_pkey,
+          program.programId
+        );
+        await program.methods
+          .likePost()
+          .accounts({
+            reactionAuthor: bob.publicKey,
+            postReaction: reaction_pkey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotPublished");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Reacting to a draft should have failed"
+      );
+    });
+
+    it("Should revise a draft in place", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_draft,
+        alice.publicKey,
+        program.programId
+      );
+      await program.methods
+        .draftSave("Final version #drafting", null, null)
+        .accounts({
+          post: post_pkey,
+          postAuthor: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.content, "Final version #drafting");
+      assert.strictEqual(postData.revisionCount, 0);
+      assert.isTrue(postData.isDraft);
+    });
+
+    it("Should not let another user save the draft", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_draft,
+        alice.publicKey,
+        program.programId
+      );
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .draftSave("Hijacked", null, null)
+          .accounts({
+            post: post_pkey,
+            postAuthor: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidOwner");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Saving someone else's draft should have failed"
+      );
+    });
+
+    it("Should publish the draft and index its hashtags", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_draft,
+        alice.publicKey,
+        program.programId
+      );
+      const [drafting_tag] = getTagAddress("drafting", program.programId);
+
+      await program.methods
+        .draftPublish()
+        .accounts({
+          post: post_pkey,
+          postAuthor: alice.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: drafting_tag, isSigner: false, isWritable: true },
+        ])
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.isFalse(postData.isDraft);
+      assert.isTrue(postData.published);
+
+      const tagData = await program.account.tag.fetch(drafting_tag);
+      assert.strictEqual(tagData.usageCount.toString(), "1");
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .draftPublish()
+          .accounts({
+            post: post_pkey,
+            postAuthor: alice.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotDraft");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Publishing a post twice should have failed"
+      );
+    });
+  });
+
+  describe("Co-authored Posts", async () => {
+    const topic_joint = "Joint announcement";
+
+    it("Should hold a co-authored post until every co-author approved", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_joint,
+        bob.publicKey,
+        program.programId
+      );
+      // Charlie signs the post right away, Alice approves it later
+      await program.methods
+        .postAdd(
+          topic_joint,
+          "We are merging our projects",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          [
+            { author: alice.publicKey, shareBps: 3000, approved: false },
+            { author: charlie.publicKey, shareBps: 3000, approved: false },
+          ]
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: charlie.publicKey, isSigner: true, isWritable: false },
+        ])
+        .signers([bob, charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.isFalse(postData.coAuthors[0].approved);
+      assert.isTrue(postData.coAuthors[1].approved);
+      assert.isFalse(postData.published);
+
+      let should_fail = "This Should Fail";
+      try {
+        const [reaction_pkey] = getPostReactionAddress(
+          charlie.publicKey,
+          post_pkey,
+          program.programId
+        );
+        await program.methods
+          .likePost()
+          .accounts({
+            reactionAuthor: charlie.publicKey,
+            postReaction: reaction_pkey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "PostNotPublished");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Reacting before every co-author approved should have failed"
+      );
+
+      const [config_pkey] = getConfigAddress(program.programId);
+      const approveTx = await program.methods
+        .coAuthorApprove()
+        .accounts({
+          post: post_pkey,
+          coAuthor: alice.publicKey,
+          config: config_pkey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const approvedData = await program.account.post.fetch(post_pkey);
+      assert.isTrue(approvedData.coAuthors[0].approved);
+      assert.isTrue(approvedData.published);
+
+      const events = await getEvents(program, approveTx);
+      assert.deepEqual(
+        events.map((event) => event.name),
+        ["coAuthorApproved", "postPublished"]
+      );
+      assert.strictEqual(
+        events[1].data.header.sequence.toNumber(),
+        events[0].data.header.sequence.toNumber() + 1
+      );
+      assert.strictEqual(
+        events[1].data.post.toString(),
+        post_pkey.toString()
+      );
+    });
+
+    it("Should split tips between the authors by their shares", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_joint,
+        bob.publicKey,
+        program.programId
+      );
+      const tipper = anchor.web3.Keypair.generate();
+      await airdrop(provider.connection, tipper.publicKey);
+
+      const balances = async () =>
+        Promise.all(
+          [bob, alice, charlie].map((user) =>
+            provider.connection.getBalance(user.publicKey)
+          )
+        );
+      const before = await balances();
+
+      await program.methods
+        .postTip(new anchor.BN(1_000_000))
+        .accounts({
+          post: post_pkey,
+          postAuthor: bob.publicKey,
+          tipper: tipper.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: alice.publicKey, isSigner: false, isWritable: true },
+          { pubkey: charlie.publicKey, isSigner: false, isWritable: true },
+        ])
+        .signers([tipper])
+        .rpc({ commitment: "confirmed" });
+
+      const after = await balances();
+      assert.strictEqual(after[0] - before[0], 400_000);
+      assert.strictEqual(after[1] - before[1], 300_000);
+      assert.strictEqual(after[2] - before[2], 300_000);
+    });
+
+    it("Cannot tip a share below the rent-exempt minimum to an empty wallet", async () => {
+      const topic_fresh = "Fresh co-author";
+      // A co-author wallet that does not exist yet
+      const co_author = anchor.web3.Keypair.generate();
+      const [post_pkey] = getPostAddress(
+        topic_fresh,
+        bob.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_fresh,
+          "Written with a newcomer",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          [{ author: co_author.publicKey, shareBps: 3000, approved: false }]
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: co_author.publicKey, isSigner: true, isWritable: false },
+        ])
+        .signers([bob, co_author])
+        .rpc({ commitment: "confirmed" });
+
+      const tipper = anchor.web3.Keypair.generate();
+      await airdrop(provider.connection, tipper.publicKey);
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .postTip(new anchor.BN(1_000_000))
+          .accounts({
+            post: post_pkey,
+            postAuthor: bob.publicKey,
+            tipper: tipper.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: co_author.publicKey, isSigner: false, isWritable: true },
+          ])
+          .signers([tipper])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "TipBelowRent");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Tipping a share below rent to an empty wallet should have failed"
+      );
+    });
+
+    it("Should require a majority to remove a co-authored post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_joint,
+        bob.publicKey,
+        program.programId
+      );
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .postRemove()
+          .accounts({
+            post: post_pkey,
+            postAuthor: bob.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "RemovalNotApproved");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Removing without a majority should have failed"
+      );
+
+      for (const voter of [alice, charlie]) {
+        await program.methods
+          .removalVote()
+          .accounts({ post: post_pkey, voter: voter.publicKey })
+          .signers([voter])
+          .rpc({ commitment: "confirmed" });
+      }
+
+      await program.methods
+        .postRemoveVoted()
+        .accounts({
+          post: post_pkey,
+          postAuthor: bob.publicKey,
+          caller: alice.publicKey,
+          payer: bob.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postInfo = await provider.connection.getAccountInfo(post_pkey);
+      assert.isNull(postInfo);
+    });
+  });
+
+  describe("Sessions", async () => {
+    const SESSION_REACT = 1;
+    const SESSION_COMMENT = 2;
+    const topic_session = "Session target";
+    const comment_session = "Sent without a wallet popup";
+    const session_key = anchor.web3.Keypair.generate();
+
+    it("Should create a session for an ephemeral key", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_session,
+        bob.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_session,
+          "React to me",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const [session_pkey] = getSessionAddress(
+        alice.publicKey,
+        session_key.publicKey,
+        program.programId
+      );
+      const now = await getChainTime(provider.connection);
+      await program.methods
+        .sessionCreate(
+          new anchor.BN(now + 3600),
+          SESSION_REACT | SESSION_COMMENT,
+          new anchor.BN(100_000_000)
+        )
+        .accounts({
+          session: session_pkey,
+          authority: alice.publicKey,
+          sessionKey: session_key.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const sessionData = await program.account.session.fetch(session_pkey);
+      assert.strictEqual(
+        sessionData.authority.toString(),
+        alice.publicKey.toString()
+      );
+      assert.strictEqual(sessionData.permissions, 3);
+    });
+
+    it("Should react and comment on behalf of the wallet", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_session,
+        bob.publicKey,
+        program.programId
+      );
+      const [session_pkey] = getSessionAddress(
+        alice.publicKey,
+        session_key.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .likePost()
+        .accounts({
+          reactionAuthor: session_key.publicKey,
+          postReaction: reaction_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          session: session_pkey,
+          payer: session_key.publicKey,
+        })
+        .signers([session_key])
+        .rpc({ commitment: "confirmed" });
+
+      const reactionData = await program.account.reactionPost.fetch(
+        reaction_pkey
+      );
+      assert.strictEqual(
+        reactionData.reactionAuthor.toString(),
+        alice.publicKey.toString()
+      );
+      // The session key is thrown away, so its wallet gets the rent back
+      assert.strictEqual(
+        reactionData.payer.toString(),
+        alice.publicKey.toString()
+      );
+
+      const [comment_pkey] = getCommentAddress(
+        comment_session,
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .commentAdd(comment_session, [], null, no_labels)
+        .accounts({
+          commentAuthor: session_key.publicKey,
+          post: post_pkey,
+          comment: comment_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          session: session_pkey,
+          payer: session_key.publicKey,
+        })
+        .signers([session_key])
+        .rpc({ commitment: "confirmed" });
+
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.strictEqual(
+        commentData.commentAuthor.toString(),
+        alice.publicKey.toString()
+      );
+    });
+
+    it("Should reject actions outside the session permissions", async () => {
+      const [post_pkey] = getPostAddress(
+        "Session post",
+        alice.publicKey,
+        program.programId
+      );
+      const [session_pkey] = getSessionAddress(
+        alice.publicKey,
+        session_key.publicKey,
+        program.programId
+      );
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .postAdd(
+            "Session post",
+            "Not allowed",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: session_key.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            session: session_pkey,
+            payer: session_key.publicKey,
+          })
+          .signers([session_key])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "SessionNotPermitted");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Posting without the post permission should have failed"
+      );
+    });
+
+    it("Should remove a reaction through the session, refunding the wallet", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_session,
+        bob.publicKey,
+        program.programId
+      );
+      const [session_pkey] = getSessionAddress(
+        alice.publicKey,
+        session_key.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      const balanceBefore = await provider.connection.getBalance(
+        alice.publicKey
+      );
+      await program.methods
+        .reactionRemovePost()
+        .accounts({
+          reactionAuthor: session_key.publicKey,
+          postReaction: reaction_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          session: session_pkey,
+          payer: alice.publicKey,
+        })
+        .signers([session_key])
+        .rpc({ commitment: "confirmed" });
+
+      assert.isNull(await provider.connection.getAccountInfo(reaction_pkey));
+      assert.isAbove(
+        await provider.connection.getBalance(alice.publicKey),
+        balanceBefore,
+        "Rent should be refunded to the session's wallet"
+      );
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.likes.toString(), "0");
+    });
+
+    it("Should revoke the session", async () => {
+      const [session_pkey] = getSessionAddress(
+        alice.publicKey,
+        session_key.publicKey,
+        program.programId
+      );
+      await program.methods
+        .sessionRevoke()
+        .accounts({
+          session: session_pkey,
+          authority: alice.publicKey,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const sessionInfo = await provider.connection.getAccountInfo(
+        session_pkey
+      );
+      assert.isNull(sessionInfo);
+    });
+  });
+
+  describe("Sponsored Accounts", async () => {
+    const topic_sponsored = "First steps";
+    // A brand new user without any SOL
+    const newcomer = anchor.web3.Keypair.generate();
+    const sponsor = provider.wallet;
+
+    it("Should let a sponsor pay for a new user's post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_sponsored,
+        newcomer.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_sponsored,
+          "Hello, I have no SOL yet",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: newcomer.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: sponsor.publicKey,
+        })
+        .signers([newcomer])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(
+        postData.postAuthor.toString(),
+        newcomer.publicKey.toString()
+      );
+      assert.strictEqual(
+        postData.payer.toString(),
+        sponsor.publicKey.toString()
+      );
+      assert.strictEqual(
+        await provider.connection.getBalance(newcomer.publicKey),
+        0
+      );
+    });
+
+    it("Should require the post's payer to fund its poll", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_sponsored,
+        newcomer.publicKey,
+        program.programId
+      );
+      const [poll_pkey] = getPollAddress(post_pkey, program.programId);
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .pollAdd(["Yes", "No"], null)
+          .accounts({
+            postAuthor: newcomer.publicKey,
+            post: post_pkey,
+            poll: poll_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([newcomer, alice])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidPayer");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Funding a poll from another payer should have failed"
+      );
+    });
+
+    it("Should refund the sponsor when the post is removed", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_sponsored,
+        newcomer.publicKey,
+        program.programId
+      );
+      const postRent = await provider.connection.getBalance(post_pkey);
+      const sponsorBalance = await provider.connection.getBalance(
+        sponsor.publicKey
+      );
+
+      await program.methods
+        .postRemove()
+        .accounts({
+          post: post_pkey,
+          postAuthor: newcomer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: sponsor.publicKey,
+        })
+        .signers([newcomer])
+        .rpc({ commitment: "confirmed" });
+
+      assert.isNull(await provider.connection.getAccountInfo(post_pkey));
+      // The sponsor also paid the transaction fee
+      assert.isAbove(
+        await provider.connection.getBalance(sponsor.publicKey),
+        sponsorBalance + postRent - 100_000
+      );
+      assert.strictEqual(
+        await provider.connection.getBalance(newcomer.publicKey),
+        0
+      );
+    });
+  });
+
+  describe("Relayed Posts", async () => {
+    const topic_relayed = "Gasless hello";
+    const content_relayed = "Signed off-chain, relayed by someone else";
+    const comment_relayed = "Relayed comment";
+    // A wallet that never pays for or signs a transaction
+    const writer = anchor.web3.Keypair.generate();
+    const relayer = provider.wallet;
+
+    it("Should create a post signed off-chain and submitted by a relayer", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_relayed,
+        writer.publicKey,
+        program.programId
+      );
+      const [nonce_pkey] = getNonceAddress(writer.publicKey, program.programId);
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey(
+        {
+          privateKey: writer.secretKey,
+          message: encodeRelayedPost(
+            program.programId,
+            0,
+            topic_relayed,
+            content_relayed
+          ),
+        }
+      );
+      await program.methods
+        .postAddRelayed(topic_relayed, content_relayed, new anchor.BN(0))
+        .accounts({
+          post: post_pkey,
+          authorNonce: nonce_pkey,
+          postAuthor: writer.publicKey,
+          payer: relayer.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .preInstructions([ed25519Ix])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(
+        postData.postAuthor.toString(),
+        writer.publicKey.toString()
+      );
+      assert.strictEqual(
+        postData.payer.toString(),
+        relayer.publicKey.toString()
+      );
+      assert.strictEqual(postData.content, content_relayed);
+      const nonceData = await program.account.authorNonce.fetch(nonce_pkey);
+      assert.strictEqual(nonceData.nonce.toString(), "1");
+    });
+
+    it("Should create a comment signed off-chain and submitted by a relayer", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_relayed,
+        writer.publicKey,
+        program.programId
+      );
+      const [comment_pkey] = getCommentAddress(
+        comment_relayed,
+        writer.publicKey,
+        post_pkey,
+        program.programId
+      );
+      const [nonce_pkey] = getNonceAddress(writer.publicKey, program.programId);
+      const { globalId } = await program.account.post.fetch(post_pkey);
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey(
+        {
+          privateKey: writer.secretKey,
+          message: encodeRelayedComment(
+            program.programId,
+            1,
+            post_pkey,
+            globalId,
+            comment_relayed
+          ),
+        }
+      );
+      await program.methods
+        .commentAddRelayed(comment_relayed, new anchor.BN(1))
+        .accounts({
+          post: post_pkey,
+          comment: comment_pkey,
+          authorNonce: nonce_pkey,
+          commentAuthor: writer.publicKey,
+          payer: relayer.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .preInstructions([ed25519Ix])
+        .rpc({ commitment: "confirmed" });
+
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.strictEqual(
+        commentData.commentAuthor.toString(),
+        writer.publicKey.toString()
+      );
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.commentCount.toString(), "1");
+    });
+
+    it("Cannot replay a used nonce", async () => {
+      const topic_replay = "Replayed hello";
+      const [post_pkey] = getPostAddress(
+        topic_replay,
+        writer.publicKey,
+        program.programId
+      );
+      const [nonce_pkey] = getNonceAddress(writer.publicKey, program.programId);
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey(
+        {
+          privateKey: writer.secretKey,
+          message: encodeRelayedPost(
+            program.programId,
+            0,
+            topic_replay,
+            content_relayed
+          ),
+        }
+      );
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .postAddRelayed(topic_replay, content_relayed, new anchor.BN(0))
+          .accounts({
+            post: post_pkey,
+            authorNonce: nonce_pkey,
+            postAuthor: writer.publicKey,
+            payer: relayer.publicKey,
+            instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidRelayNonce");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Replaying a relayed message should have failed"
+      );
+    });
+
+    it("Cannot relay a post signed by someone else", async () => {
+      const topic_forged = "Forged hello";
+      const [post_pkey] = getPostAddress(
+        topic_forged,
+        writer.publicKey,
+        program.programId
+      );
+      const [nonce_pkey] = getNonceAddress(writer.publicKey, program.programId);
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey(
+        {
+          privateKey: alice.secretKey,
+          message: encodeRelayedPost(
+            program.programId,
+            2,
+            topic_forged,
+            content_relayed
+          ),
+        }
+      );
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .postAddRelayed(topic_forged, content_relayed, new anchor.BN(2))
+          .accounts({
+            post: post_pkey,
+            authorNonce: nonce_pkey,
+            postAuthor: writer.publicKey,
+            payer: relayer.publicKey,
+            instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(
+          err.error.errorCode.code,
+          "InvalidSignatureInstruction"
+        );
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Relaying a post signed by another wallet should have failed"
+      );
+    });
+
+    it("Cannot relay a comment signed for an earlier post at the same address", async () => {
+      const topic_recreated = "Recreated hello";
+      const [post_pkey] = getPostAddress(
+        topic_recreated,
+        alice.publicKey,
+        program.programId
+      );
+      const [comment_pkey] = getCommentAddress(
+        comment_relayed,
+        writer.publicKey,
+        post_pkey,
+        program.programId
+      );
+      const [nonce_pkey] = getNonceAddress(writer.publicKey, program.programId);
+      const addPost = () =>
+        program.methods
+          .postAdd(
+            topic_recreated,
+            "Removed and posted again",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+
+      // The comment is signed for the first post, which is then replaced
+      await addPost();
+      const stale = await program.account.post.fetch(post_pkey);
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey(
+        {
+          privateKey: writer.secretKey,
+          message: encodeRelayedComment(
+            program.programId,
+            2,
+            post_pkey,
+            stale.globalId,
+            comment_relayed
+          ),
+        }
+      );
+      await program.methods
+        .postRemove()
+        .accounts({
+          post: post_pkey,
+          postAuthor: alice.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+      await addPost();
+      const current = await program.account.post.fetch(post_pkey);
+      assert.notStrictEqual(
+        current.globalId.toString(),
+        stale.globalId.toString()
+      );
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .commentAddRelayed(comment_relayed, new anchor.BN(2))
+          .accounts({
+            post: post_pkey,
+            comment: comment_pkey,
+            authorNonce: nonce_pkey,
+            commentAuthor: writer.publicKey,
+            payer: relayer.publicKey,
+            instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(
+          err.error.errorCode.code,
+          "InvalidSignatureInstruction"
+        );
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Relaying a comment signed for a replaced post should have failed"
+      );
+    });
+  });
+
+  describe("Batch Reactions", async () => {
+    const topics_batch = ["Batch target one", "Batch target two"];
+    const comment_batch = "Batch comment target";
+    const writable = (pubkey: PublicKey) => ({
+      pubkey,
+      isSigner: false,
+      isWritable: true,
+    });
+
+    const getTargets = () => {
+      const posts = topics_batch.map(
+        (topic) => getPostAddress(topic, bob.publicKey, program.programId)[0]
+      );
+      const [comment_pkey] = getCommentAddress(
+        comment_batch,
+        bob.publicKey,
+        posts[0],
+        program.programId
+      );
+      const postReactions = posts.map(
+        (post) =>
+          getPostReactionAddress(charlie.publicKey, post, program.programId)[0]
+      );
+      const [commentReaction] = getCommentReactionAddress(
+        charlie.publicKey,
+        comment_pkey,
+        program.programId
+      );
+      return { posts, comment_pkey, postReactions, commentReaction };
+    };
+
+    it("Should react to several posts and a comment at once", async () => {
+      const { posts, comment_pkey, postReactions, commentReaction } =
+        getTargets();
+      for (let i = 0; i < topics_batch.length; i++) {
+        await program.methods
+          .postAdd(
+            topics_batch[i],
+            "Curate me",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: bob.publicKey,
+            post: posts[i],
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc({ commitment: "confirmed" });
+      }
+      await program.methods
+        .commentAdd(comment_batch, [], null, no_labels)
+        .accounts({
+          commentAuthor: bob.publicKey,
+          post: posts[0],
+          comment: comment_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      await program.methods
+        .reactionBatch([
+          { target: { post: {} }, reaction: { like: {} } },
+          { target: { post: {} }, reaction: { dislike: {} } },
+          { target: { comment: {} }, reaction: { like: {} } },
+        ])
+        .accounts({
+          reactionAuthor: charlie.publicKey,
+          payer: charlie.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          writable(posts[0]),
+          writable(postReactions[0]),
+          writable(posts[1]),
+          writable(postReactions[1]),
+          writable(posts[0]),
+          writable(comment_pkey),
+          writable(commentReaction),
+        ])
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const firstPost = await program.account.post.fetch(posts[0]);
+      assert.strictEqual(firstPost.likes.toString(), "1");
+      const secondPost = await program.account.post.fetch(posts[1]);
+      assert.strictEqual(secondPost.dislikes.toString(), "1");
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.strictEqual(commentData.likes.toString(), "1");
+      const reactionData = await program.account.reactionPost.fetch(
+        postReactions[0]
+      );
+      assert.strictEqual(
+        reactionData.reactionAuthor.toString(),
+        charlie.publicKey.toString()
+      );
+    });
+
+    it("Should skip targets that were already reacted to", async () => {
+      const { posts, postReactions } = getTargets();
+      await program.methods
+        .reactionBatch([{ target: { post: {} }, reaction: { like: {} } }])
+        .accounts({
+          reactionAuthor: charlie.publicKey,
+          payer: charlie.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([writable(posts[0]), writable(postReactions[0])])
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(posts[0]);
+      assert.strictEqual(postData.likes.toString(), "1");
+    });
+
+    it("Cannot refund a batch removal to another payer", async () => {
+      const { posts, postReactions } = getTargets();
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .reactionBatch([{ target: { post: {} }, reaction: null }])
+          .accounts({
+            reactionAuthor: charlie.publicKey,
+            payer: charlie.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts([
+            writable(posts[0]),
+            writable(postReactions[0]),
+            writable(alice.publicKey),
+          ])
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidPayer");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Refunding a reaction to another payer should have failed"
+      );
+    });
+
+    it("Should remove several reactions at once", async () => {
+      const { posts, comment_pkey, postReactions, commentReaction } =
+        getTargets();
+      await program.methods
+        .reactionBatch([
+          { target: { post: {} }, reaction: null },
+          { target: { post: {} }, reaction: null },
+          { target: { comment: {} }, reaction: null },
+        ])
+        .accounts({
+          reactionAuthor: charlie.publicKey,
+          payer: charlie.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          writable(posts[0]),
+          writable(postReactions[0]),
+          writable(charlie.publicKey),
+          writable(posts[1]),
+          writable(postReactions[1]),
+          writable(charlie.publicKey),
+          writable(comment_pkey),
+          writable(commentReaction),
+          writable(charlie.publicKey),
+        ])
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const firstPost = await program.account.post.fetch(posts[0]);
+      assert.strictEqual(firstPost.likes.toString(), "0");
+      const secondPost = await program.account.post.fetch(posts[1]);
+      assert.strictEqual(secondPost.dislikes.toString(), "0");
+      const commentData = await program.account.comment.fetch(comment_pkey);
+      assert.strictEqual(commentData.likes.toString(), "0");
+      assert.isNull(
+        await provider.connection.getAccountInfo(postReactions[0])
+      );
+    });
+  });
+
+  describe("Post Hooks", async () => {
+    const hookProgram = anchor.workspace.depressHook.programId as PublicKey;
+    const topic_hooked = "Hooked post";
+
+    it("Should let the author register a hook program", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_hooked,
+        bob.publicKey,
+        program.programId
+      );
+      await program.methods
+        .postAdd(
+          topic_hooked,
+          "Only likes here",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .postSetHook(hookProgram)
+        .accounts({
+          post: post_pkey,
+          postAuthor: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(
+        postData.hookProgram.toString(),
+        hookProgram.toString()
+      );
+    });
+
+    it("Should invoke the hook when reacting", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_hooked,
+        bob.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .likePost()
+        .accounts({
+          reactionAuthor: alice.publicKey,
+          postReaction: reaction_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+          hookProgram,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.likes.toString(), "1");
+    });
+
+    it("Cannot skip the post's hook program", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_hooked,
+        bob.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        charlie.publicKey,
+        post_pkey,
+        program.programId
+      );
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .likePost()
+          .accounts({
+            reactionAuthor: charlie.publicKey,
+            postReaction: reaction_pkey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "MissingHookProgram");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Reacting without the hook program should have failed"
+      );
+    });
+
+    it("Cannot pass writable DePress accounts to the hook", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_hooked,
+        bob.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        charlie.publicKey,
+        post_pkey,
+        program.programId
+      );
+      const [config_pkey] = getConfigAddress(program.programId);
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .likePost()
+          .accounts({
+            reactionAuthor: charlie.publicKey,
+            postReaction: reaction_pkey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+            hookProgram,
+          })
+          .remainingAccounts([
+            { pubkey: config_pkey, isSigner: false, isWritable: true },
+          ])
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "HookAccountNotAllowed");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Passing the config to the hook should have failed"
+      );
+    });
+
+    it("Cannot react when the hook rejects it", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_hooked,
+        bob.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        charlie.publicKey,
+        post_pkey,
+        program.programId
+      );
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .dislikePost()
+          .accounts({
+            reactionAuthor: charlie.publicKey,
+            postReaction: reaction_pkey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: charlie.publicKey,
+            hookProgram,
+          })
+          .signers([charlie])
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "DislikesDisabled");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "A dislike rejected by the hook should have failed"
+      );
+    });
+
+    it("Should stop invoking the hook once cleared", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_hooked,
+        bob.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        charlie.publicKey,
+        post_pkey,
+        program.programId
+      );
+      await program.methods
+        .postSetHook(null)
+        .accounts({
+          post: post_pkey,
+          postAuthor: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .dislikePost()
+        .accounts({
+          reactionAuthor: charlie.publicKey,
+          postReaction: reaction_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: charlie.publicKey,
+        })
+        .signers([charlie])
+        .rpc({ commitment: "confirmed" });
+
+      const postData = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(postData.dislikes.toString(), "1");
+    });
+  });
+
+  describe("Event Schema", async () => {
+    const topic_events = "Indexed post";
+
+    it("Should emit versioned, sequenced events with typed reactions", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_events,
+        bob.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      const [config_pkey] = getConfigAddress(program.programId);
+      const sequenceBefore = (
+        await program.account.config.fetch(config_pkey)
+      ).eventSequence.toNumber();
+
+      const postTx = await program.methods
+        .postAdd(
+          topic_events,
+          "Index me",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: bob.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+      const likeTx = await program.methods
+        .likePost()
+        .accounts({
+          reactionAuthor: alice.publicKey,
+          postReaction: reaction_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+          config: config_pkey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const created = (await getEvents(program, postTx))
+        .filter((event) => event.name === "postCreated")
+        .map((event) => event.data);
+      const reacted = (await getEvents(program, likeTx))
+        .filter((event) => event.name === "reactionPostAdded")
+        .map((event) => event.data);
+
+      assert.strictEqual(created.length, 1);
+      assert.strictEqual(reacted.length, 1);
+      assert.strictEqual(created[0].header.version, 2);
+      assert.strictEqual(
+        created[0].header.sequence.toNumber(),
+        sequenceBefore + 1
+      );
+      assert.strictEqual(
+        reacted[0].header.sequence.toNumber(),
+        sequenceBefore + 2
+      );
+      assert.isAbove(reacted[0].header.timestamp.toNumber(), 0);
+      assert.deepEqual(reacted[0].reaction, { like: {} });
+    });
+
+    it("Should emit unsequenced events without the config", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_events,
+        bob.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        alice.publicKey,
+        post_pkey,
+        program.programId
+      );
+      const [config_pkey] = getConfigAddress(program.programId);
+      const sequenceBefore = (
+        await program.account.config.fetch(config_pkey)
+      ).eventSequence.toNumber();
+
+      const tx = await program.methods
+        .reactionRemovePost()
+        .accounts({
+          reactionAuthor: alice.publicKey,
+          postReaction: reaction_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+          config: null,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const removed = (await getEvents(program, tx))
+        .filter((event) => event.name === "reactionPostRemoved")
+        .map((event) => event.data);
+      assert.strictEqual(removed.length, 1);
+      assert.strictEqual(removed[0].header.sequence.toNumber(), 0);
+      assert.isAbove(removed[0].header.timestamp.toNumber(), 0);
+
+      // The config was not written, so its sequence is untouched
+      const sequenceAfter = (
+        await program.account.config.fetch(config_pkey)
+      ).eventSequence.toNumber();
+      assert.strictEqual(sequenceAfter, sequenceBefore);
+    });
+  });
+
+  describe("Post Sequence", async () => {
+    const topic_first = "Sequence one";
+    const topic_second = "Sequence two";
+
+    it("Should number posts globally and index them by id", async () => {
+      const [config_pkey] = getConfigAddress(program.programId);
+      const countBefore = (await program.account.config.fetch(config_pkey))
+        .postCount;
+
+      for (const topic of [topic_first, topic_second]) {
+        const [post_pkey] = getPostAddress(
+          topic,
+          alice.publicKey,
+          program.programId
+        );
+        await program.methods
+          .postAdd(
+            topic,
+            "Numbered",
+            [],
+            null,
+            [],
+            no_labels,
+            null,
+            null,
+            false,
+            []
+          )
+          .accounts({
+            postAuthor: alice.publicKey,
+            post: post_pkey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            payer: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc({ commitment: "confirmed" });
+      }
+
+      const config = await program.account.config.fetch(config_pkey);
+      assert.strictEqual(
+        config.postCount.toString(),
+        countBefore.addn(2).toString()
+      );
+
+      // Page backwards from the newest post
+      const newest = config.postCount.subn(1);
+      const [newest_index] = getIndexAddress(newest, program.programId);
+      const newestEntry = await program.account.postIndex.fetch(newest_index);
+      const newestPost = await program.account.post.fetch(newestEntry.post);
+      assert.strictEqual(newestPost.topic, topic_second);
+      assert.strictEqual(newestPost.globalId.toString(), newest.toString());
+
+      const [previous_index] = getIndexAddress(
+        newest.subn(1),
+        program.programId
+      );
+      const previousEntry = await program.account.postIndex.fetch(
+        previous_index
+      );
+      const previousPost = await program.account.post.fetch(
+        previousEntry.post
+      );
+      assert.strictEqual(previousPost.topic, topic_first);
+    });
+
+    it("Should close the index entry together with the post", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_second,
+        alice.publicKey,
+        program.programId
+      );
+      const { globalId } = await program.account.post.fetch(post_pkey);
+      const [index_pkey] = getIndexAddress(globalId, program.programId);
+
+      await program.methods
+        .postRemove()
+        .accounts({
+          post: post_pkey,
+          postAuthor: alice.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+
+      const indexInfo = await provider.connection.getAccountInfo(index_pkey);
+      assert.isNull(indexInfo);
+    });
+  });
+
+  describe("Account Layouts", async () => {
+    const topic_layout = "Filterable";
+
+    it("Should match counters and parents with memcmp filters", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_layout,
+        alice.publicKey,
+        program.programId
+      );
+      const [reaction_pkey] = getPostReactionAddress(
+        bob.publicKey,
+        post_pkey,
+        program.programId
+      );
+
+      await program.methods
+        .postAdd(
+          topic_layout,
+          "Find me by my likes",
+          [],
+          null,
+          [],
+          no_labels,
+          null,
+          null,
+          false,
+          []
+        )
+        .accounts({
+          postAuthor: alice.publicKey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .likePost()
+        .accounts({
+          reactionAuthor: bob.publicKey,
+          postReaction: reaction_pkey,
+          post: post_pkey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          payer: bob.publicKey,
+        })
+        .signers([bob])
+        .rpc({ commitment: "confirmed" });
+
+      const oneLike = anchor.utils.bytes.bs58.encode(
+        new anchor.BN(1).toArrayLike(Buffer, "le", 8)
+      );
+      const posts = await program.account.post.all([
+        {
+          memcmp: {
+            offset: POST_AUTHOR_OFFSET,
+            bytes: alice.publicKey.toBase58(),
+          },
+        },
+        { memcmp: { offset: POST_LIKES_OFFSET, bytes: oneLike } },
+        {
+          memcmp: {
+            offset: POST_IS_DRAFT_OFFSET,
+            bytes: anchor.utils.bytes.bs58.encode([0]),
+          },
+        },
+      ]);
+      assert.include(
+        posts.map((post) => post.publicKey.toString()),
+        post_pkey.toString()
+      );
+      posts.forEach((post) => {
+        assert.strictEqual(post.account.likes.toString(), "1");
+        assert.isFalse(post.account.isDraft);
+      });
+
+      const reactions = await program.account.reactionPost.all([
+        {
+          memcmp: {
+            offset: REACTION_PARENT_OFFSET,
+            bytes: post_pkey.toBase58(),
+          },
+        },
+      ]);
+      assert.strictEqual(reactions.length, 1);
+      assert.strictEqual(
+        reactions[0].account.reactionAuthor.toString(),
+        bob.publicKey.toString()
+      );
+    });
+
+    it("Should reject migrating an account already in the current layout", async () => {
+      const [post_pkey] = getPostAddress(
+        topic_layout,
+        alice.publicKey,
+        program.programId
+      );
+
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .accountMigrate()
+          .accounts({
+            account: post_pkey,
+            payer: provider.wallet.publicKey,
+            postIndex: null,
+          })
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotLegacyAccount");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Migrating a current account should have failed"
+      );
+    });
+  });
+
+  describe("Account Migration", async () => {
+    const fixture_author = fixtureKey("author");
+
+    before(async function () {
+      // The historical accounts are only loaded by the local validator
+      const info = await provider.connection.getAccountInfo(
+        fixtureKey("payerless_post")
+      );
+      if (!info) {
+        this.skip();
+      }
+    });
+
+    it("Should reject migrating a post without its post index", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .accountMigrate()
+          .accounts({
+            account: fixtureKey("payerless_post"),
+            payer: provider.wallet.publicKey,
+            postIndex: null,
+          })
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "InvalidPostIndex");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Migrating a post without its post index should have failed"
+      );
+    });
+
+    it("Should migrate a payerless post, taking the next global id", async () => {
+      const post_pkey = fixtureKey("payerless_post");
+      const [config_pkey] = getConfigAddress(program.programId);
+      const { postCount } = await program.account.config.fetch(config_pkey);
+      const [index_pkey] = getIndexAddress(postCount, program.programId);
+
+      const migrated = await migrateFixture(
+        program,
+        "payerless_post",
+        index_pkey
+      );
+      await checkMigrated(program, "Post", migrated, ACCOUNT_SPACE.PostIndex);
+
+      const post = await program.account.post.fetch(post_pkey);
+      assert.strictEqual(post.version, 1);
+      assert.strictEqual(post.postAuthor.toString(), fixture_author.toString());
+      assert.strictEqual(post.payer.toString(), fixture_author.toString());
+      assert.strictEqual(post.topic, "Legacy topic");
+      assert.strictEqual(post.content, "Written before payers existed");
+      assert.strictEqual(post.likes.toString(), "3");
+      assert.strictEqual(post.dislikes.toString(), "1");
+      assert.strictEqual(post.commentCount, 2);
+      assert.strictEqual(post.totalLength, post.content.length);
+      assert.strictEqual(post.globalId.toString(), postCount.toString());
+      assert.isTrue(post.published);
+      assert.isFalse(post.isDraft);
+      assert.isTrue(post.coAuthors.length === 0 && post.hashtags.length === 0);
+      assert.strictEqual(post.bump, 254);
+
+      const index = await program.account.postIndex.fetch(index_pkey);
+      assert.strictEqual(index.version, 1);
+      assert.strictEqual(index.post.toString(), post_pkey.toString());
+
+      const events = (await getEvents(program, migrated.tx))
+        .filter((event) => event.name === "accountMigrated")
+        .map((event) => event.data);
+      assert.strictEqual(events.length, 1);
+      assert.strictEqual(events[0].account.toString(), post_pkey.toString());
+    });
+
+    it("Should migrate payerless comments and reactions, their authors as payers", async () => {
+      for (const [name, account] of [
+        ["payerless_comment", "Comment"],
+        ["payerless_reaction_post", "ReactionPost"],
+        ["payerless_reaction_comment", "ReactionComment"],
+      ]) {
+        await checkMigrated(
+          program,
+          account,
+          await migrateFixture(program, name)
+        );
+      }
+
+      const comment = await program.account.comment.fetch(
+        fixtureKey("payerless_comment")
+      );
+      assert.strictEqual(comment.version, 1);
+      assert.strictEqual(comment.payer.toString(), fixture_author.toString());
+      assert.strictEqual(
+        comment.parentPost.toString(),
+        fixtureKey("payerless_post").toString()
+      );
+      assert.strictEqual(comment.content, "Legacy comment");
+      assert.strictEqual(comment.likes.toString(), "1");
+      assert.strictEqual(comment.mentions.length, 0);
+      assert.isNull(comment.attachment);
+      assert.strictEqual(comment.bump, 253);
+
+      const post_reaction = await program.account.reactionPost.fetch(
+        fixtureKey("payerless_reaction_post")
+      );
+      assert.strictEqual(post_reaction.version, 1);
+      assert.strictEqual(
+        post_reaction.payer.toString(),
+        fixture_author.toString()
+      );
+      assert.deepEqual(post_reaction.reaction, { like: {} });
+      assert.strictEqual(post_reaction.bump, 252);
+
+      const comment_reaction = await program.account.reactionComment.fetch(
+        fixtureKey("payerless_reaction_comment")
+      );
+      assert.strictEqual(comment_reaction.version, 1);
+      assert.strictEqual(
+        comment_reaction.payer.toString(),
+        fixture_author.toString()
+      );
+      assert.strictEqual(
+        comment_reaction.parentComment.toString(),
+        fixtureKey("payerless_comment").toString()
+      );
+      assert.deepEqual(comment_reaction.reaction, { dislike: {} });
+    });
+
+    it("Should reject migrating an account twice", async () => {
+      let should_fail = "This Should Fail";
+      try {
+        await program.methods
+          .accountMigrate()
+          .accounts({
+            account: fixtureKey("payerless_reaction_post"),
+            payer: provider.wallet.publicKey,
+            postIndex: null,
+          })
+          .rpc({ commitment: "confirmed" });
+      } catch (error) {
+        const err = anchor.AnchorError.parse(error.logs);
+        assert.strictEqual(err.error.errorCode.code, "NotLegacyAccount");
+        should_fail = "Failed";
+      }
+      assert.strictEqual(
+        should_fail,
+        "Failed",
+        "Migrating an account twice should have failed"
+      );
+    });
+  });
+});
+
+async function getChainTime(connection: any): Promise<number> {
+  const slot = await connection.getSlot("confirmed");
+  return await connection.getBlockTime(slot);
+}
+
+async function airdrop(connection: any, address: any, amount = 1000000000) {
+  await connection.confirmTransaction(
+    await connection.requestAirdrop(address, amount),
+    "confirmed"
+  );
+}
+
+function getCommentAddress(
+  comment_content: string,
+  author: PublicKey,
+  parent_tweet: PublicKey,
+  programID: PublicKey
+) {
+  let hexString = crypto
+    .createHash("sha256")
+    .update(comment_content, "utf-8")
+    .digest("hex");
+  let content_seed = Uint8Array.from(Buffer.from(hexString, "hex"));
+
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(COMMENT_SEED),
+      author.toBuffer(),
+      content_seed,
+      parent_tweet.toBuffer(),
+    ],
+    programID
+  );
+}
+
+function getPostAddress(
+  topic: string,
+  author: PublicKey,
+  programID: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(POST_SEED),
+      anchor.utils.bytes.utf8.encode(topic),
+      author.toBuffer(),
+    ],
+    programID
+  );
+}
+
+function getTagAddress(tag: string, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(TAG_SEED),
+      anchor.utils.bytes.utf8.encode(tag),
+    ],
+    programID
+  );
+}
+
+function getRepostAddress(
+  reposter: PublicKey,
+  original_post: PublicKey,
+  programID: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(REPOST_SEED),
+      reposter.toBuffer(),
+      original_post.toBuffer(),
+    ],
+    programID
+  );
+}
+
+function getPollAddress(post: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode(POLL_SEED), post.toBuffer()],
+    programID
+  );
+}
+
+function getPollVoteAddress(
+  voter: PublicKey,
+  poll: PublicKey,
+  programID: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(POLL_VOTE_SEED),
+      voter.toBuffer(),
+      poll.toBuffer(),
+    ],
+    programID
+  );
+}
+
+function getProfileAddress(author: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode(PROFILE_SEED), author.toBuffer()],
+    programID
+  );
+}
+
+function getRevisionAddress(
+  post: PublicKey,
+  revision_number: number,
+  programID: PublicKey
+) {
+  const revision_seed = Buffer.alloc(4);
+  revision_seed.writeUInt32LE(revision_number, 0);
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(REVISION_SEED),
+      post.toBuffer(),
+      revision_seed,
+    ],
+    programID
+  );
+}
+
+function getChunkAddress(
+  post: PublicKey,
+  index: number,
+  programID: PublicKey
+) {
+  const index_seed = Buffer.alloc(4);
+  index_seed.writeUInt32LE(index, 0);
+  return PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode(CHUNK_SEED), post.toBuffer(), index_seed],
+    programID
+  );
+}
+
+function getSessionAddress(
+  authority: PublicKey,
+  session_key: PublicKey,
+  programID: PublicKey
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(SESSION_SEED),
+      authority.toBuffer(),
+      session_key.toBuffer(),
+    ],
+    programID
+  );
+}
+
+function getConfigAddress(programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode(CONFIG_SEED)],
+    programID
+  );
+}
+
+function getIndexAddress(globalId: anchor.BN, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode(INDEX_SEED),
+      globalId.toArrayLike(Buffer, "le", 8),
+    ],
+    programID
+  );
+}
+
+// Accounts written by tests/fixtures/generate.js
+function fixtureKey(name: string) {
+  return new PublicKey(
+    crypto.createHash("sha256").update(`depress fixture ${name}`).digest()
+  );
+}
+
+// Migrates a fixture with the provider wallet as payer, returning the account
+// before and after along with the lamports the wallet paid besides the fee
+async function migrateFixture(
+  program: Program<Depress>,
+  name: string,
+  postIndex: PublicKey | null = null
+) {
+  const connection = program.provider.connection;
+  const payer = program.provider.publicKey;
+  const account = fixtureKey(name);
+  const before = await connection.getAccountInfo(account, "confirmed");
+  const balanceBefore = await connection.getBalance(payer, "confirmed");
+
+  const tx = await program.methods
+    .accountMigrate()
+    .accounts({ account, payer, postIndex })
+    .rpc({ commitment: "confirmed" });
+
+  const after = await connection.getAccountInfo(account, "confirmed");
+  const balanceAfter = await connection.getBalance(payer, "confirmed");
+  const { meta } = await connection.getTransaction(tx, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  return { tx, before, after, paid: balanceBefore - balanceAfter - meta.fee };
+}
+
+// Checks that a migrated account has the discriminator and size of the current
+// layout of `accountName`, and that the payer topped its rent up exactly, plus
+// the rent of any account of `createdSpace` bytes created on the way
+async function checkMigrated(
+  program: Program<Depress>,
+  accountName: string,
+  { before, after, paid },
+  createdSpace = 0
+) {
+  const connection = program.provider.connection;
+  const { discriminator } = program.idl.accounts.find(
+    (account) => account.name === accountName
+  );
+  assert.isTrue(
+    after.data.subarray(0, 8).equals(Buffer.from(discriminator)),
+    accountName
+  );
+  assert.strictEqual(
+    after.data.length,
+    ACCOUNT_SPACE[accountName],
+    accountName
+  );
+  assert.isAbove(after.data.length, before.data.length, accountName);
+
+  const rent = await connection.getMinimumBalanceForRentExemption(
+    after.data.length
+  );
+  const createdRent = createdSpace
+    ? await connection.getMinimumBalanceForRentExemption(createdSpace)
+    : 0;
+  assert.strictEqual(after.lamports, rent, accountName);
+  assert.strictEqual(
+    paid,
+    after.lamports - before.lamports + createdRent,
+    accountName
+  );
+}
+
+function getNonceAddress(author: PublicKey, programID: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode(NONCE_SEED), author.toBuffer()],
+    programID
+  );
+}
+
+function encodeRelayedPost(
+  programID: PublicKey,
+  nonce: number,
+  topic: string,
+  content: string
+) {
+  return Buffer.concat([
+    Buffer.from([0]),
+    programID.toBuffer(),
+    new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+    encodeString(topic),
+    encodeString(content),
+  ]);
+}
+
+function encodeRelayedComment(
+  programID: PublicKey,
+  nonce: number,
+  parent_post: PublicKey,
+  parent_global_id: anchor.BN,
+  content: string
+) {
+  return Buffer.concat([
+    Buffer.from([1]),
+    programID.toBuffer(),
+    new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+    parent_post.toBuffer(),
+    parent_global_id.toArrayLike(Buffer, "le", 8),
+    encodeString(content),
+  ]);
+}
+
+function encodeString(value: string) {
+  const bytes = Buffer.from(value, "utf8");
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length);
+  return Buffer.concat([len, bytes]);
+}
+
+// Decodes the events emitted through self-CPI from the inner instructions
+async function getEvents(program: Program<Depress>, signature: string) {
+  const tx = await program.provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const accountKeys = tx.transaction.message.getAccountKeys();
+  const events = [];
+  for (const inner of tx.meta.innerInstructions) {
+    for (const ix of inner.instructions) {
+      if (!accountKeys.get(ix.programIdIndex).equals(program.programId)) {
+        continue;
+      }
+      // Strip the 8-byte event instruction tag
+      const data = Buffer.from(anchor.utils.bytes.bs58.decode(ix.data));
+      const event = program.coder.events.decode(
+        anchor.utils.bytes.base64.encode(data.subarray(8))
+      );
+      if (event) {
+        events.push(event);
+      }
+    }
+  }
+  return events;
 }
 
 function getPostReactionAddress(
@@ -0,0 +1,207 @@
+// Writes one account per historical layout for the migration tests, loaded by
+// the local validator through `[[test.validator.account]]` in Anchor.toml, and
+// prints those entries.
+//
+//   node tests/fixtures/generate.js
+//
+// Depends on nothing but Node, so layouts are encoded by hand: each is a list
+// of [field, type] with the sizes Anchor's `InitSpace` gives them, and every
+// account is allocated at exactly the discriminator plus that space, as the
+// program does.
+
+const crypto = require("crypto");
+const fs = require("fs");
+const path = require("path");
+
+const PROGRAM_ID = "5aQmhcFhVmgtmCdGtffRuMYL9R1WsARAtukzxUttPKKN";
+
+// Types
+const u8 = { size: 1, write: (v) => Buffer.from([v]) };
+const u32 = { size: 4, write: (v) => int(v, 4) };
+const u64 = { size: 8, write: (v) => int(v, 8) };
+const pubkey = { size: 32, write: (v) => v };
+const string = (max) => ({
+  size: 4 + max,
+  write: (v) => Buffer.concat([int(Buffer.byteLength(v), 4), Buffer.from(v)]),
+});
+const struct = (fields) => ({
+  size: fields.reduce((size, [, type]) => size + type.size, 0),
+  write: (v) =>
+    Buffer.concat(
+      fields.map(([name, type]) => {
+        if (!(name in v)) throw new Error(`Missing field ${name}`);
+        return type.write(v[name]);
+      })
+    ),
+});
+
+// Little-endian unsigned integer of `size` bytes
+function int(value, size) {
+  const buffer = Buffer.alloc(8);
+  buffer.writeBigUInt64LE(BigInt(value));
+  return buffer.subarray(0, size);
+}
+
+// Constants of programs/depress/src/constants.rs
+const TOPIC_LENGTH = 32;
+const CONTENT_LENGTH = 500;
+const COMMENT_LENGTH = 100;
+
+// `ReactionType`, 0 for `Like` and 1 for `Dislike`
+const ReactionType = u8;
+
+// Layouts from before accounts recorded their payer, written by 0.1
+const PAYERLESS = {
+  Post: struct([
+    ["postAuthor", pubkey],
+    ["topic", string(TOPIC_LENGTH)],
+    ["content", string(CONTENT_LENGTH)],
+    ["likes", u64],
+    ["dislikes", u64],
+    ["commentCount", u32],
+    ["bump", u8],
+  ]),
+  Comment: struct([
+    ["commentAuthor", pubkey],
+    ["parentPost", pubkey],
+    ["content", string(COMMENT_LENGTH)],
+    ["likes", u64],
+    ["dislikes", u64],
+    ["bump", u8],
+  ]),
+  ReactionPost: struct([
+    ["reactionAuthor", pubkey],
+    ["parentPost", pubkey],
+    ["reaction", ReactionType],
+    ["bump", u8],
+  ]),
+  ReactionComment: struct([
+    ["reactionAuthor", pubkey],
+    ["parentComment", pubkey],
+    ["reaction", ReactionType],
+    ["bump", u8],
+  ]),
+};
+
+// Deterministic keys, mirrored by `fixtureKey` in tests/depress.ts
+function key(name) {
+  return crypto.createHash("sha256").update(`depress fixture ${name}`).digest();
+}
+
+const author = key("author");
+
+const fixtures = [
+  // Without payers, under the discriminators of the struct names
+  {
+    name: "payerless_post",
+    account: "Post",
+    layout: PAYERLESS.Post,
+    value: {
+      postAuthor: author,
+      topic: "Legacy topic",
+      content: "Written before payers existed",
+      likes: 3,
+      dislikes: 1,
+      commentCount: 2,
+      bump: 254,
+    },
+  },
+  {
+    name: "payerless_comment",
+    account: "Comment",
+    layout: PAYERLESS.Comment,
+    value: {
+      commentAuthor: author,
+      parentPost: key("payerless_post"),
+      content: "Legacy comment",
+      likes: 1,
+      dislikes: 0,
+      bump: 253,
+    },
+  },
+  {
+    name: "payerless_reaction_post",
+    account: "ReactionPost",
+    layout: PAYERLESS.ReactionPost,
+    value: {
+      reactionAuthor: author,
+      parentPost: key("payerless_post"),
+      reaction: 0,
+      bump: 252,
+    },
+  },
+  {
+    name: "payerless_reaction_comment",
+    account: "ReactionComment",
+    layout: PAYERLESS.ReactionComment,
+    value: {
+      reactionAuthor: author,
+      parentComment: key("payerless_comment"),
+      reaction: 1,
+      bump: 251,
+    },
+  },
+];
+
+function discriminator(name) {
+  return crypto
+    .createHash("sha256")
+    .update(`account:${name}`)
+    .digest()
+    .subarray(0, 8);
+}
+
+const ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+function bs58(buffer) {
+  let value = BigInt("0x" + buffer.toString("hex"));
+  let encoded = "";
+  while (value > 0n) {
+    encoded = ALPHABET[Number(value % 58n)] + encoded;
+    value /= 58n;
+  }
+  for (const byte of buffer) {
+    if (byte !== 0) break;
+    encoded = "1" + encoded;
+  }
+  return encoded;
+}
+
+// Rent-exempt minimum under the default rent, two years of 3480 lamports per
+// byte, counting the 128 bytes of account metadata
+function rentExempt(space) {
+  return (128 + space) * 3480 * 2;
+}
+
+const directory = __dirname;
+const entries = [];
+for (const { name, account: accountName, layout, value } of fixtures) {
+  const data = Buffer.alloc(8 + layout.size);
+  Buffer.concat([discriminator(accountName), layout.write(value)]).copy(data);
+
+  const pubkey = bs58(key(name));
+  const account = {
+    pubkey,
+    account: {
+      lamports: rentExempt(data.length),
+      data: [data.toString("base64"), "base64"],
+      owner: PROGRAM_ID,
+      executable: false,
+      rentEpoch: 0,
+      space: data.length,
+    },
+  };
+  fs.writeFileSync(
+    path.join(directory, `${name}.json`),
+    JSON.stringify(account, null, 2) + "\n"
+  );
+  entries.push(
+    [
+      "[[test.validator.account]]",
+      `address = "${pubkey}"`,
+      `filename = "tests/fixtures/${name}.json"`,
+    ].join("\n")
+  );
+}
+
+console.log(entries.join("\n\n"));